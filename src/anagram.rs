@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::iter;
+
+use crate::blocks::{self, Block, Position};
+use crate::hangul::{compat_compound, KoreanSentence};
+
+/// Returns the basic jamo of the Hangul in `word` with their counts, splitting
+/// compound vowels and tails as typed on a 2-set keyboard (ㅘ is ㅗ and ㅏ).
+///
+/// # Examples
+///
+/// ```
+/// use jamo::anagram::jamo_multiset;
+/// let jamo = jamo_multiset("과일");
+/// assert_eq!(Some(&1), jamo.get(&'ㅗ'));
+/// assert_eq!(6, jamo.values().sum::<usize>());
+/// ```
+pub fn jamo_multiset(word: &str) -> BTreeMap<char, usize> {
+    let mut counts = BTreeMap::new();
+    for c in KoreanSentence::new(word).strokes().chars() {
+        if blocks::block(c) == Some(Block::Compatibility) {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Returns whether `a` and `b` are made of the same jamo.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::anagram::is_jamo_anagram;
+/// assert!(is_jamo_anagram("사랑", "상라"));
+/// assert!(is_jamo_anagram("닭", "닥ㄹ"));
+/// assert!(!is_jamo_anagram("사랑", "사람"));
+/// ```
+pub fn is_jamo_anagram(a: &str, b: &str) -> bool {
+    jamo_multiset(a) == jamo_multiset(b)
+}
+
+/// Returns up to `limit` syllable sequences that use every jamo of `word`
+/// exactly once, each syllable a lead, a vowel and an optional tail. Pairs
+/// of jamo may combine into compound vowels and tails.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::anagram::{arrangements, is_jamo_anagram};
+/// let words = arrangements("사랑", 100);
+/// assert!(words.contains(&"랑사".to_string()));
+/// assert!(words.iter().all(|w| is_jamo_anagram(w, "사랑")));
+/// assert_eq!(vec!["국"], arrangements("국", 10));
+/// ```
+pub fn arrangements(word: &str, limit: usize) -> Vec<String> {
+    let mut results = vec![];
+    arrange(&mut jamo_multiset(word), &mut String::new(), &mut results, limit);
+    results
+}
+
+fn arrange(counts: &mut BTreeMap<char, usize>, current: &mut String,
+           results: &mut Vec<String>, limit: usize) {
+    if results.len() >= limit {
+        return;
+    }
+    if counts.values().all(|&n| n == 0) {
+        if !current.is_empty() {
+            results.push(current.clone());
+        }
+        return;
+    }
+    for lead in available(counts) {
+        let lead_index = match blocks::index(lead, Position::Lead) {
+            Some(i) => i,
+            None => continue,
+        };
+        take(counts, &[lead]);
+        for vowel in groups(counts, Position::Vowel) {
+            take(counts, &vowel.1);
+            let tails = groups(counts, Position::Tail);
+            for tail in iter::once(None).chain(tails.iter().map(Some)) {
+                let (tail_index, tail_jamo) = tail.map_or((0, &[][..]), |(i, jamo)| (*i, &jamo[..]));
+                take(counts, tail_jamo);
+                if let Ok(c) = blocks::compose_indices(lead_index, vowel.0, tail_index) {
+                    current.push(c);
+                    arrange(counts, current, results, limit);
+                    current.pop();
+                }
+                give(counts, tail_jamo);
+            }
+            give(counts, &vowel.1);
+        }
+        give(counts, &[lead]);
+    }
+}
+
+/// Returns the jamo with a count left.
+fn available(counts: &BTreeMap<char, usize>) -> Vec<char> {
+    counts.iter().filter(|(_, &n)| n > 0).map(|(&c, _)| c).collect()
+}
+
+/// Returns the vowels or tails that one or two of the remaining jamo make,
+/// with their index and the jamo they use.
+fn groups(counts: &BTreeMap<char, usize>, position: Position) -> Vec<(usize, Vec<char>)> {
+    let jamo = available(counts);
+    let mut groups = jamo.iter()
+        .filter_map(|&c| blocks::index(c, position).map(|i| (i, vec![c])))
+        .collect::<Vec<(usize, Vec<char>)>>();
+    for &a in &jamo {
+        for &b in &jamo {
+            if a == b && counts[&a] < 2 {
+                continue;
+            }
+            if let Some(i) = compat_compound(a, b).and_then(|c| blocks::index(c, position)) {
+                groups.push((i, vec![a, b]));
+            }
+        }
+    }
+    groups
+}
+
+fn take(counts: &mut BTreeMap<char, usize>, jamo: &[char]) {
+    for c in jamo {
+        if let Some(n) = counts.get_mut(c) {
+            *n -= 1;
+        }
+    }
+}
+
+fn give(counts: &mut BTreeMap<char, usize>, jamo: &[char]) {
+    for c in jamo {
+        *counts.entry(*c).or_insert(0) += 1;
+    }
+}
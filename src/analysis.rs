@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+
+use crate::hangul::{KoreanSentence, Letter};
+
+/// Occurrence counts of characters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Counts {
+    counts: BTreeMap<char, usize>,
+}
+
+impl Counts {
+    pub fn add(&mut self, c: char) {
+        *self.counts.entry(c).or_insert(0) += 1;
+    }
+
+    pub fn get(&self, c: char) -> usize {
+        self.counts.get(&c).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Returns the share of `c` among all counted characters, or 0 when empty.
+    pub fn ratio(&self, c: char) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.get(c) as f64 / total as f64,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, usize)> + '_ {
+        self.counts.iter().map(|(&c, &n)| (c, n))
+    }
+
+    /// Returns the `k` most frequent characters, most frequent first.
+    pub fn top(&self, k: usize) -> Vec<(char, usize)> {
+        let mut sorted = self.iter().collect::<Vec<(char, usize)>>();
+        sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        sorted.truncate(k);
+        sorted
+    }
+}
+
+impl Extend<char> for Counts {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        for c in iter {
+            self.add(c);
+        }
+    }
+}
+
+/// Jamo and syllable statistics of a text. Jamo are counted as compatibility
+/// jamo, so ㄱ as a lead and ㄱ as a tail are both `'ㄱ'`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Frequencies {
+    pub leads: Counts,
+    pub vowels: Counts,
+    pub tails: Counts,
+    pub syllables: Counts,
+}
+
+impl Frequencies {
+    /// Formats the `k` most frequent entries of each category as a text report.
+    pub fn report(&self, k: usize) -> String {
+        [("Leads", &self.leads), ("Vowels", &self.vowels),
+            ("Tails", &self.tails), ("Syllables", &self.syllables)]
+            .iter()
+            .map(|(name, counts)| {
+                let rows = counts.top(k).iter()
+                    .map(|(c, n)| format!("{}\t{}\t{:.2}%\n", c, n, counts.ratio(*c) * 100.0))
+                    .collect::<String>();
+                format!("[{}] total {}\n{}", name, counts.total(), rows)
+            })
+            .collect::<Vec<String>>()
+            .join("")
+    }
+}
+
+/// Counts leads, vowels, tails and whole syllables in `text`.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::analysis::frequencies;
+/// let f = frequencies("안녕 안녕하세요");
+/// assert_eq!(2, f.syllables.get('안'));
+/// assert_eq!(vec![('ㅇ', 3)], f.leads.top(1));
+/// assert_eq!(2, f.tails.get('ㄴ'));
+/// ```
+pub fn frequencies(text: &str) -> Frequencies {
+    let mut f = Frequencies::default();
+    for c in text.chars() {
+        if let Letter::HangulLetter(h) = Letter::new(c) {
+            f.syllables.add(c);
+            f.leads.extend(h.lead().compat_char());
+            f.vowels.extend(h.vowel().compat_char());
+            f.tails.extend(h.tail().compat_char());
+        }
+    }
+    f
+}
+
+/// Marks the end of each syllable in [`ngrams_with_boundaries`].
+pub const SYLLABLE_BOUNDARY: char = '|';
+
+/// Returns the jamo n-grams of `text`, taken over the stream of conjoining
+/// jamo with non-Hangul characters kept in place.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::analysis::ngrams;
+/// let grams = ngrams("간나", 2);
+/// assert_eq!(4, grams.len());
+/// assert_eq!("\u{1161}\u{11ab}", grams[1]); // ㅏ followed by tail ㄴ
+/// ```
+pub fn ngrams(text: &str, n: usize) -> Vec<String> {
+    windows(&jamo_stream(text, false), n)
+}
+
+/// Like [`ngrams`], with [`SYLLABLE_BOUNDARY`] inserted after every syllable.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::analysis::ngrams_with_boundaries;
+/// let grams = ngrams_with_boundaries("가나", 3);
+/// assert_eq!("\u{1161}|\u{1102}", grams[1]); // ㅏ, boundary, lead ㄴ
+/// ```
+pub fn ngrams_with_boundaries(text: &str, n: usize) -> Vec<String> {
+    windows(&jamo_stream(text, true), n)
+}
+
+fn jamo_stream(text: &str, boundaries: bool) -> Vec<char> {
+    let mut stream = vec![];
+    for c in text.chars() {
+        stream.extend(Letter::new(c).hangul_string().chars());
+        if boundaries && Letter::new(c).is_hangul() {
+            stream.push(SYLLABLE_BOUNDARY);
+        }
+    }
+    stream
+}
+
+fn windows(stream: &[char], n: usize) -> Vec<String> {
+    if n == 0 {
+        return vec![];
+    }
+    stream.windows(n).map(|w| w.iter().collect()).collect()
+}
+
+/// The share of each script among the non-whitespace characters of a text,
+/// from [`script_profile`]. The shares add up to 1 unless the text is empty.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScriptProfile {
+    pub hangul: f64,
+    /// Conjoining, compatibility and halfwidth jamo.
+    pub jamo: f64,
+    pub latin: f64,
+    pub cjk: f64,
+    pub digits: f64,
+    /// Punctuation, symbols and other scripts.
+    pub other: f64,
+}
+
+impl ScriptProfile {
+    /// Returns whether Hangul syllables and jamo make up at least `threshold`
+    /// of the letters, leaving digits and punctuation out of the count.
+    pub fn is_mostly_korean(&self, threshold: f64) -> bool {
+        let letters = self.hangul + self.jamo + self.latin + self.cjk;
+        letters > 0.0 && (self.hangul + self.jamo) / letters >= threshold
+    }
+}
+
+/// Measures the scripts of `text`, for routing multilingual text.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::analysis::script_profile;
+/// let profile = script_profile("서울 Seoul 2024 漢");
+/// assert_eq!(2.0 / 12.0, profile.hangul);
+/// assert_eq!(4.0 / 12.0, profile.digits);
+/// assert!(!profile.is_mostly_korean(0.5));
+/// assert!(script_profile("2024년 3월 ㅋㅋ").is_mostly_korean(0.9));
+/// ```
+pub fn script_profile(text: &str) -> ScriptProfile {
+    let mut counts = [0usize; 6];
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        let script = match c {
+            '\u{ac00}'..='\u{d7a3}' => 0,
+            _ if crate::blocks::block(c).is_some() => 1,
+            _ if c.is_alphabetic() && (c.is_ascii() || ('\u{c0}'..='\u{24f}').contains(&c)) => 2,
+            '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{f900}'..='\u{faff}' => 3,
+            _ if c.is_numeric() => 4,
+            _ => 5,
+        };
+        counts[script] += 1;
+    }
+    let total = counts.iter().sum::<usize>().max(1) as f64;
+    let share = |i: usize| counts[i] as f64 / total;
+    ScriptProfile {
+        hangul: share(0),
+        jamo: share(1),
+        latin: share(2),
+        cjk: share(3),
+        digits: share(4),
+        other: share(5),
+    }
+}
+
+/// Returns the share of Hangul syllables among the letters of `sentence`
+/// that are not whitespace, or 0 when there are none. See
+/// [`KoreanSentence::non_hangul_positions`] for the rest.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::analysis::hangul_ratio;
+/// use jamo::hangul::KoreanSentence;
+/// assert_eq!(0.5, hangul_ratio(&KoreanSentence::new("안녕 hi")));
+/// assert_eq!(0.0, hangul_ratio(&KoreanSentence::new(" ")));
+/// ```
+pub fn hangul_ratio(sentence: &KoreanSentence) -> f64 {
+    let hangul = sentence.letters().iter().filter(|l| l.is_hangul()).count();
+    match hangul + sentence.non_hangul_positions().len() {
+        0 => 0.0,
+        n => hangul as f64 / n as f64,
+    }
+}
@@ -0,0 +1,914 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blocks;
+use crate::hangul::KoreanSentence;
+use crate::rules::{Rule, RuleProfile, STANDARD};
+
+#[derive(Clone)]
+pub struct CorpusOptions {
+    /// Apply the pronunciation rules before producing output.
+    pub apply_rules: bool,
+    /// The rules applied when `apply_rules` is set.
+    pub rules: Cow<'static, [Rule]>,
+    /// A profile applied in place of `rules`, with its reductions and
+    /// dialect shifts.
+    pub profile: Option<RuleProfile>,
+    /// Number of lines processed together; with the `parallel` feature each
+    /// chunk is spread over the available threads.
+    pub chunk_size: usize,
+    /// Told the running totals after every chunk and at the end of the input.
+    pub progress: Option<Arc<dyn ProgressSink>>,
+    /// Once set to true, processing stops before the next chunk; see
+    /// [`Corpus::cancelled`] for where it stopped.
+    pub cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Default for CorpusOptions {
+    fn default() -> Self {
+        Self {
+            apply_rules: true,
+            rules: Cow::Borrowed(&STANDARD),
+            profile: None,
+            chunk_size: 1024,
+            progress: None,
+            cancel: None,
+        }
+    }
+}
+
+impl fmt::Debug for CorpusOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CorpusOptions")
+            .field("apply_rules", &self.apply_rules)
+            .field("rules", &self.rules)
+            .field("profile", &self.profile)
+            .field("chunk_size", &self.chunk_size)
+            .field("progress", &self.progress.is_some())
+            .field("cancel", &self.cancel)
+            .finish()
+    }
+}
+
+/// Running totals of a batch job, as reported to a [`ProgressSink`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// Input bytes consumed, as read and counting line ends. Lines given as
+    /// strings count their UTF-8 length plus one for the line end.
+    pub bytes: u64,
+    pub lines: usize,
+    pub rules_fired: usize,
+}
+
+impl Progress {
+    fn add(&mut self, output: &Output, bytes: usize) {
+        self.bytes += bytes as u64;
+        self.lines += 1;
+        self.rules_fired += output.rules_fired;
+    }
+}
+
+/// Receives the progress of the batch APIs, e.g. to draw a progress bar.
+/// Any `Fn(Progress)` closure is a sink.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use jamo::batch::{process_corpus, CorpusOptions, Progress};
+/// let last = Arc::new(Mutex::new(Progress::default()));
+/// let sink = last.clone();
+/// let options = CorpusOptions {
+///     chunk_size: 1,
+///     progress: Some(Arc::new(move |p: Progress| *sink.lock().unwrap() = p)),
+///     ..CorpusOptions::default()
+/// };
+/// let lines = vec!["좋아요".to_string(), "안녕".to_string()];
+/// process_corpus(lines.into_iter(), options).for_each(drop);
+/// assert_eq!(Progress { bytes: 17, lines: 2, rules_fired: 3 }, *last.lock().unwrap());
+/// ```
+pub trait ProgressSink: Send + Sync {
+    fn progress(&self, progress: Progress);
+}
+
+impl<F: Fn(Progress) + Send + Sync> ProgressSink for F {
+    fn progress(&self, progress: Progress) {
+        self(progress)
+    }
+}
+
+impl CorpusOptions {
+    fn report(&self, progress: Progress) {
+        if let Some(sink) = &self.progress {
+            sink.progress(progress);
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    fn roman(&self, text: &str) -> String {
+        if text.is_empty() {
+            return String::new();
+        }
+        let sentence = KoreanSentence::new(text);
+        if self.apply_rules {
+            self.with_rules(sentence).applied().roman()
+        } else {
+            sentence.roman()
+        }
+    }
+
+    /// Gives `sentence` the profile, or else the rules, to apply.
+    fn with_rules(&self, sentence: KoreanSentence) -> KoreanSentence {
+        match self.profile {
+            Some(profile) => sentence.with_profile(profile),
+            None => sentence.with_rules(self.rules.clone()),
+        }
+    }
+}
+
+/// The processed forms of one input line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Output {
+    /// Zero-based position of the line in the input.
+    pub index: usize,
+    pub source: String,
+    pub roman: String,
+    pub jamo: String,
+    pub hangul: String,
+    /// The source line was not valid UTF-8 and had invalid bytes replaced with U+FFFD.
+    pub lossy: bool,
+    /// Number of times a rule fired on the line.
+    pub rules_fired: usize,
+}
+
+impl Output {
+    pub fn new(index: usize, source: String, options: &CorpusOptions) -> Self {
+        let mut sentence = KoreanSentence::new(&source);
+        let mut fired = vec![];
+        if options.apply_rules {
+            sentence = options.with_rules(sentence).applied_traced(&mut fired);
+        }
+        Self {
+            index,
+            roman: sentence.roman(),
+            jamo: sentence.jamo(),
+            hangul: sentence.hangul_string(),
+            source,
+            lossy: false,
+            rules_fired: fired.len(),
+        }
+    }
+}
+
+/// Processes lines lazily, one chunk at a time, yielding outputs in input order.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::batch::{process_corpus, CorpusOptions};
+/// let lines = vec!["좋아요".to_string(), "안녕".to_string()];
+/// let outputs = process_corpus(lines.into_iter(), CorpusOptions::default())
+///     .collect::<Vec<_>>();
+/// assert_eq!("joayo", outputs[0].roman);
+/// assert_eq!(1, outputs[1].index);
+/// ```
+pub fn process_corpus<I: Iterator<Item = String>>(lines: I, options: CorpusOptions) -> Corpus<I> {
+    Corpus { lines, options, next_index: 0, ready: VecDeque::new(), progress: Progress::default() }
+}
+
+pub struct Corpus<I> {
+    lines: I,
+    options: CorpusOptions,
+    next_index: usize,
+    ready: VecDeque<Output>,
+    progress: Progress,
+}
+
+impl<I> Corpus<I> {
+    /// Returns the work done before processing stopped if it was cancelled
+    /// through [`CorpusOptions::cancel`]: its `lines` is the index of the
+    /// first line left unprocessed. The lines processed before the stop are
+    /// still yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use jamo::batch::{process_corpus, CorpusOptions};
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let options = CorpusOptions { chunk_size: 2, cancel: Some(cancel.clone()), ..CorpusOptions::default() };
+    /// let lines = vec!["가"; 5].into_iter().map(String::from);
+    /// let mut corpus = process_corpus(lines, options);
+    /// corpus.next();
+    /// cancel.store(true, Ordering::Relaxed);
+    /// assert_eq!(1, corpus.by_ref().count());
+    /// assert_eq!(Some(2), corpus.cancelled().map(|p| p.lines));
+    /// ```
+    pub fn cancelled(&self) -> Option<Progress> {
+        Some(self.progress).filter(|_| self.options.is_cancelled())
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for Corpus<I> {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Output> {
+        if self.ready.is_empty() {
+            if self.options.is_cancelled() {
+                return None;
+            }
+            let chunk = self.lines.by_ref()
+                .take(self.options.chunk_size.max(1))
+                .collect::<Vec<String>>();
+            let first = self.next_index;
+            self.next_index += chunk.len();
+            self.ready.extend(process_chunk(first, chunk, &self.options));
+            if !self.ready.is_empty() {
+                for output in &self.ready {
+                    self.progress.add(output, output.source.len() + 1);
+                }
+                self.options.report(self.progress);
+            }
+        }
+        self.ready.pop_front()
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn process_chunk(first: usize, chunk: Vec<String>, options: &CorpusOptions) -> Vec<Output> {
+    chunk.into_iter().enumerate()
+        .map(|(i, line)| Output::new(first + i, line, options))
+        .collect()
+}
+
+#[cfg(feature = "parallel")]
+fn process_chunk(first: usize, chunk: Vec<String>, options: &CorpusOptions) -> Vec<Output> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let per_thread = chunk.len().div_ceil(threads).max(1);
+    let mut lines = chunk.into_iter().enumerate().collect::<Vec<(usize, String)>>();
+    let mut parts = vec![];
+    while !lines.is_empty() {
+        let rest = lines.split_off(per_thread.min(lines.len()));
+        parts.push(std::mem::replace(&mut lines, rest));
+    }
+    std::thread::scope(|scope| {
+        let handles = parts.into_iter().map(|part| scope.spawn(move || {
+            part.into_iter()
+                .map(|(i, line)| Output::new(first + i, line, options))
+                .collect::<Vec<Output>>()
+        })).collect::<Vec<_>>();
+        // Joining in spawn order keeps the output in input order.
+        handles.into_iter()
+            .flat_map(|h| h.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect()
+    })
+}
+
+/// Processes a file like [`process_corpus`], reading it through a memory map
+/// so that only the lines being processed are ever copied, for corpora too
+/// large to load into a `String`.
+///
+/// Lines that are not valid UTF-8 are decoded lossily and flagged with
+/// [`Output::lossy`].
+///
+/// The file must not be modified while it is being processed.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::batch::{process_file_mmap, CorpusOptions};
+/// let path = std::env::temp_dir().join("jamo-mmap-example.txt");
+/// std::fs::write(&path, "좋아요\r\n안녕\n").unwrap();
+/// let outputs = process_file_mmap(&path, CorpusOptions::default()).unwrap().collect::<Vec<_>>();
+/// assert_eq!(vec!["joayo", "annyeong"], outputs.iter().map(|o| o.roman.as_str()).collect::<Vec<_>>());
+/// ```
+#[cfg(feature = "mmap")]
+pub fn process_file_mmap<P: AsRef<std::path::Path>>(path: P, options: CorpusOptions) -> io::Result<MmapCorpus> {
+    let file = std::fs::File::open(path)?;
+    // The map is only read, and the caller is told not to modify the file meanwhile.
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(MmapCorpus { map, position: 0, options, next_index: 0, ready: VecDeque::new(), progress: Progress::default() })
+}
+
+#[cfg(feature = "mmap")]
+pub struct MmapCorpus {
+    map: memmap2::Mmap,
+    position: usize,
+    options: CorpusOptions,
+    next_index: usize,
+    ready: VecDeque<Output>,
+    progress: Progress,
+}
+
+#[cfg(feature = "mmap")]
+impl MmapCorpus {
+    /// Like [`Corpus::cancelled`]; `bytes` is the offset in the file of the
+    /// first line left unprocessed.
+    pub fn cancelled(&self) -> Option<Progress> {
+        Some(self.progress).filter(|_| self.options.is_cancelled())
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl Iterator for MmapCorpus {
+    type Item = Output;
+
+    fn next(&mut self) -> Option<Output> {
+        if self.ready.is_empty() {
+            if self.options.is_cancelled() {
+                return None;
+            }
+            let (mut lines, mut read) = (vec![], vec![]);
+            while lines.len() < self.options.chunk_size.max(1) && self.position < self.map.len() {
+                let rest = &self.map[self.position..];
+                // A newline byte never occurs inside a multi-byte UTF-8 sequence,
+                // so every line starts and ends on a char boundary.
+                let end = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+                let consumed = (end + 1).min(rest.len());
+                self.position += consumed;
+                let line = rest[..end].strip_suffix(b"\r").unwrap_or(&rest[..end]);
+                let (line, invalid) = match std::str::from_utf8(line) {
+                    Ok(line) => (line.to_string(), false),
+                    Err(_) => (String::from_utf8_lossy(line).into_owned(), true),
+                };
+                lines.push(line);
+                read.push((invalid, consumed));
+            }
+            let first = self.next_index;
+            self.next_index += lines.len();
+            let outputs = process_chunk(first, lines, &self.options);
+            for (mut output, (lossy, consumed)) in outputs.into_iter().zip(read) {
+                output.lossy = lossy;
+                self.progress.add(&output, consumed);
+                self.ready.push_back(output);
+            }
+            if !self.ready.is_empty() {
+                self.options.report(self.progress);
+            }
+        }
+        self.ready.pop_front()
+    }
+}
+
+/// What [`convert_file`] did and how fast.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Throughput {
+    /// Input bytes consumed, as in [`Progress::bytes`].
+    pub bytes: u64,
+    pub lines: usize,
+    pub elapsed: Duration,
+    /// The conversion was cancelled through [`CorpusOptions::cancel`]. The
+    /// output then holds the first `lines` lines, `bytes` bytes of input.
+    pub cancelled: bool,
+    /// Lines that were not valid UTF-8 and had invalid bytes replaced.
+    pub lossy_lines: usize,
+}
+
+impl Throughput {
+    pub fn bytes_per_second(&self) -> f64 {
+        self.bytes as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+// Bytes of input each thread gets per round of `convert_file`.
+const CONVERT_CHUNK: usize = 1 << 20;
+
+/// Writes the romanization of each line of `input` to `output`, with the
+/// standard rules, on `threads` threads (at least one).
+///
+/// The input is read a round at a time and split at line ends, which are
+/// always safe boundaries for the rules; the chunks are processed in
+/// parallel and written back in input order, so memory stays bounded
+/// however large the file is. A line of more than a mebibyte is cut at a
+/// char boundary and romanized in pieces, without rules across the cuts.
+/// Invalid UTF-8 is replaced with U+FFFD and counted in
+/// [`Throughput::lossy_lines`].
+///
+/// # Examples
+///
+/// ```
+/// use jamo::batch::convert_file;
+/// let dir = std::env::temp_dir();
+/// let (input, output) = (dir.join("jamo-convert-in.txt"), dir.join("jamo-convert-out.txt"));
+/// std::fs::write(&input, "좋아요\n안녕\n").unwrap();
+/// let throughput = convert_file(&input, &output, 4).unwrap();
+/// assert_eq!(2, throughput.lines);
+/// assert_eq!("joayo\nannyeong\n", std::fs::read_to_string(&output).unwrap());
+/// ```
+pub fn convert_file<P: AsRef<Path>, Q: AsRef<Path>>(input: P, output: Q, threads: usize) -> io::Result<Throughput> {
+    convert_file_with(input, output, threads, &CorpusOptions::default())
+}
+
+/// Like [`convert_file`], with the rules of `options`.
+pub fn convert_file_with<P: AsRef<Path>, Q: AsRef<Path>>(input: P, output: Q, threads: usize,
+                                                          options: &CorpusOptions) -> io::Result<Throughput> {
+    let start = Instant::now();
+    let threads = threads.max(1);
+    let mut reader = BufReader::new(File::open(input)?);
+    let mut writer = BufWriter::new(File::create(output)?);
+    let mut progress = Progress::default();
+    let mut round = Vec::with_capacity(threads * CONVERT_CHUNK);
+    // The start of a char cut off at the end of the last round.
+    let mut carry = vec![];
+    let mut lossy_lines = 0;
+    while !options.is_cancelled() {
+        round.clear();
+        round.append(&mut carry);
+        reader.by_ref().take((threads * CONVERT_CHUNK - round.len()) as u64).read_to_end(&mut round)?;
+        if round.is_empty() {
+            break;
+        }
+        let mut cut = false;
+        if !round.ends_with(b"\n") {
+            let read = reader.by_ref().take(CONVERT_CHUNK as u64).read_until(b'\n', &mut round)?;
+            cut = read == CONVERT_CHUNK && !round.ends_with(b"\n");
+        }
+        if cut {
+            carry = round.split_off(round.len() - incomplete_char(&round));
+        }
+        let chunks = split_lines(&round, threads);
+        let last = chunks.len() - 1;
+        let converted = std::thread::scope(|scope| {
+            let handles = chunks.into_iter().enumerate()
+                .map(|(i, chunk)| scope.spawn(move || romanize_lines(chunk, options, cut && i == last)))
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|h| h.join()).collect::<Result<Vec<_>, _>>()
+        }).map_err(|_| io::Error::other("convert worker panicked"))?;
+        for (text, done, lossy) in converted {
+            writer.write_all(text.as_bytes())?;
+            progress.bytes += done.bytes;
+            progress.lines += done.lines;
+            progress.rules_fired += done.rules_fired;
+            lossy_lines += lossy;
+        }
+        options.report(progress);
+    }
+    writer.flush()?;
+    Ok(Throughput {
+        bytes: progress.bytes,
+        lines: progress.lines,
+        elapsed: start.elapsed(),
+        cancelled: options.is_cancelled(),
+        lossy_lines,
+    })
+}
+
+/// Returns the number of bytes at the end of `bytes` that start a UTF-8
+/// sequence without finishing it.
+fn incomplete_char(bytes: &[u8]) -> usize {
+    let start = bytes.iter().rev().take(4).position(|&b| b & 0xc0 != 0x80).map(|i| bytes.len() - 1 - i);
+    let start = match start {
+        Some(start) => start,
+        None => return 0,
+    };
+    let needed = match bytes[start] {
+        b if b >= 0xf0 => 4,
+        b if b >= 0xe0 => 3,
+        b if b >= 0xc0 => 2,
+        _ => 1,
+    };
+    if bytes.len() - start < needed { bytes.len() - start } else { 0 }
+}
+
+/// Splits `bytes` into at most `n` runs of whole lines of about equal size.
+fn split_lines(mut bytes: &[u8], n: usize) -> Vec<&[u8]> {
+    let size = bytes.len().div_ceil(n).max(1);
+    let mut chunks = vec![];
+    while !bytes.is_empty() {
+        let end = bytes.iter().skip(size - 1).position(|&b| b == b'\n')
+            .map_or(bytes.len(), |i| size + i);
+        let (chunk, rest) = bytes.split_at(end.min(bytes.len()));
+        chunks.push(chunk);
+        bytes = rest;
+    }
+    chunks
+}
+
+/// Returns the romanization of each line of `bytes`, newline-terminated
+/// unless `open_end` says the last line goes on in the next round, the work
+/// done and the number of lines with invalid UTF-8.
+fn romanize_lines(bytes: &[u8], options: &CorpusOptions, open_end: bool) -> (String, Progress, usize) {
+    let mut text = String::with_capacity(bytes.len());
+    let mut progress = Progress { bytes: bytes.len() as u64, ..Progress::default() };
+    let mut lossy = 0;
+    let mut lines = bytes.strip_suffix(b"\n").unwrap_or(bytes).split(|&b| b == b'\n').peekable();
+    while let Some(line) = lines.next() {
+        let line = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line));
+        lossy += matches!(line, Cow::Owned(_)) as usize;
+        let mut sentence = KoreanSentence::new(&line);
+        let mut fired = vec![];
+        if options.apply_rules {
+            sentence = options.with_rules(sentence).applied_traced(&mut fired);
+        }
+        text.push_str(&sentence.roman());
+        progress.rules_fired += fired.len();
+        if open_end && lines.peek().is_none() {
+            break;
+        }
+        text.push('\n');
+        progress.lines += 1;
+    }
+    (text, progress, lossy)
+}
+
+/// Runs arbitrary bytes through every stage of the pipeline and returns the
+/// processed forms, as an entry point for fuzzing:
+///
+/// ```text
+/// fuzz_target!(|data: &[u8]| { jamo::batch::process_any(data); });
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use jamo::batch::process_any;
+/// let output = process_any(b"\xea\xb0\x80\xff");
+/// assert_eq!("ga\u{fffd}", output.roman);
+/// assert!(output.lossy);
+/// ```
+pub fn process_any(data: &[u8]) -> Output {
+    let text = String::from_utf8_lossy(data);
+    let lossy = matches!(text, Cow::Owned(_));
+    let (repaired, _) = KoreanSentence::repaired(&text);
+    let mut sentence = repaired.applied();
+    let middle = sentence.letters().len() / 2;
+    sentence.replace(middle..middle + 1, &text);
+    let _ = (sentence.pronounced(), sentence.kana(), sentence.format_table(), sentence.roman_alignment());
+    let _ = crate::diff::diff(&repaired, &sentence);
+    let _ = (crate::search::search_key(&text), crate::search::phonetic_hash(&text));
+    let _ = (crate::analysis::frequencies(&text), crate::analysis::ngrams_with_boundaries(&text, 3));
+    let _ = crate::keyboard::finger_load(&text, crate::keyboard::Layout::Sebeolsik390);
+    let _ = crate::loanword::latin_candidates(&text, 3);
+    let mut output = Output::new(0, text.into_owned(), &CorpusOptions::default());
+    output.lossy = lossy;
+    output
+}
+
+/// Reads lines from a `BufRead` and yields their processed forms one at a time.
+///
+/// Lines that are not valid UTF-8 are decoded lossily and flagged with
+/// [`Output::lossy`] rather than ending the iteration.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::batch::JamoLines;
+/// let input: &[u8] = b"\xec\xa2\x8b\xec\x95\x84\xec\x9a\x94\n\xff\n";
+/// let outputs = JamoLines::new(input).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!("joayo", outputs[0].roman);
+/// assert!(outputs[1].lossy);
+/// ```
+pub struct JamoLines<R> {
+    reader: R,
+    options: CorpusOptions,
+    index: usize,
+    buf: Vec<u8>,
+    progress: Progress,
+}
+
+impl<R: BufRead> JamoLines<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, CorpusOptions::default())
+    }
+
+    pub fn with_options(reader: R, options: CorpusOptions) -> Self {
+        Self { reader, options, index: 0, buf: vec![], progress: Progress::default() }
+    }
+
+    /// Like [`Corpus::cancelled`]; `bytes` is the offset in the input of the
+    /// first line left unread. Cancellation is checked before every line.
+    pub fn cancelled(&self) -> Option<Progress> {
+        Some(self.progress).filter(|_| self.options.is_cancelled())
+    }
+}
+
+impl<R: BufRead> Iterator for JamoLines<R> {
+    type Item = io::Result<Output>;
+
+    fn next(&mut self) -> Option<io::Result<Output>> {
+        if self.options.is_cancelled() {
+            return None;
+        }
+        self.buf.clear();
+        match self.reader.read_until(b'\n', &mut self.buf) {
+            Ok(0) => {
+                if self.progress.lines % self.options.chunk_size.max(1) != 0 {
+                    self.options.report(self.progress);
+                }
+                None
+            }
+            Ok(n) => {
+                if self.buf.ends_with(b"\n") {
+                    self.buf.pop();
+                    if self.buf.ends_with(b"\r") {
+                        self.buf.pop();
+                    }
+                }
+                let (line, lossy) = match String::from_utf8(self.buf.clone()) {
+                    Ok(line) => (line, false),
+                    Err(_) => (String::from_utf8_lossy(&self.buf).into_owned(), true),
+                };
+                let mut output = Output::new(self.index, line, &self.options);
+                output.lossy = lossy;
+                self.index += 1;
+                self.progress.bytes += n as u64;
+                self.progress.lines += 1;
+                self.progress.rules_fired += output.rules_fired;
+                if self.progress.lines % self.options.chunk_size.max(1) == 0 {
+                    self.options.report(self.progress);
+                }
+                Some(Ok(output))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Romanizes text arriving in chunks of any size, emitting output as soon as
+/// no later input can change it.
+///
+/// Rules, reductions and shifts never reach past a character that is not
+/// Hangul, so the trailing run of Hangul is held back and everything before
+/// it is romanized as a sentence, exactly as [`Output::new`] would. A run
+/// longer than [`MAX_HELD`] chars is cut there regardless, and rules do not
+/// join its pieces.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::batch::{CorpusOptions, StreamingRomanizer};
+/// use jamo::hangul::KoreanSentence;
+/// use jamo::rules::RuleProfile;
+/// let mut romanizer = StreamingRomanizer::new(CorpusOptions::default());
+/// let mut roman = String::new();
+/// for chunk in ["좋", "아요 학", "", "년"] {
+///     roman.push_str(&romanizer.push(chunk));
+/// }
+/// roman.push_str(&romanizer.finish());
+/// assert_eq!("joayo hagnyeon", roman);
+///
+/// let options = CorpusOptions { profile: Some(RuleProfile::Colloquial), ..CorpusOptions::default() };
+/// let mut romanizer = StreamingRomanizer::new(options);
+/// let mut roman = romanizer.push("그것");
+/// roman.push_str(&romanizer.push("을 먹을 것이"));
+/// roman.push_str(&romanizer.push("다"));
+/// roman.push_str(&romanizer.finish());
+/// let whole = KoreanSentence::new("그것을 먹을 것이다").with_profile(RuleProfile::Colloquial);
+/// assert_eq!(whole.applied().roman(), roman);
+/// ```
+pub struct StreamingRomanizer {
+    options: CorpusOptions,
+    pending: String,
+}
+
+/// The most chars of Hangul a [`StreamingRomanizer`] holds back.
+pub const MAX_HELD: usize = 4096;
+
+impl StreamingRomanizer {
+    pub fn new(options: CorpusOptions) -> Self {
+        Self { options, pending: String::new() }
+    }
+
+    /// Adds `chunk` and returns the romanization of the input that is now final.
+    pub fn push(&mut self, chunk: &str) -> String {
+        self.pending.push_str(chunk);
+        let cut = self.pending.char_indices().rev()
+            .take_while(|&(_, c)| blocks::is_hangul(c) || blocks::is_filler(c))
+            .take(MAX_HELD)
+            .last()
+            .map_or(self.pending.len(), |(i, _)| i);
+        let rest = self.pending.split_off(cut);
+        let roman = self.options.roman(&self.pending);
+        self.pending = rest;
+        roman
+    }
+
+    /// Returns the romanization of the input held back, at the end of the stream.
+    pub fn finish(&mut self) -> String {
+        let roman = self.options.roman(&self.pending);
+        self.pending.clear();
+        roman
+    }
+}
+
+/// A writer that romanizes the UTF-8 text written to it and forwards the
+/// result to an inner writer.
+///
+/// Codepoints split across writes are put back together, and the last word
+/// is held back for the rules until more text arrives or [`finish`] is called.
+///
+/// [`finish`]: RomanizingWriter::finish
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use jamo::batch::{CorpusOptions, RomanizingWriter};
+/// let mut writer = RomanizingWriter::new(vec![], CorpusOptions::default());
+/// let bytes = "좋아요".as_bytes();
+/// writer.write_all(&bytes[..4]).unwrap();
+/// writer.write_all(&bytes[4..]).unwrap();
+/// assert_eq!(b"joayo", &writer.finish().unwrap()[..]);
+/// ```
+pub struct RomanizingWriter<W: Write> {
+    inner: W,
+    romanizer: StreamingRomanizer,
+    partial: Vec<u8>,
+}
+
+impl<W: Write> RomanizingWriter<W> {
+    pub fn new(inner: W, options: CorpusOptions) -> Self {
+        Self { inner, romanizer: StreamingRomanizer::new(options), partial: vec![] }
+    }
+
+    /// Writes out the text held back and returns the inner writer.
+    ///
+    /// Fails with `InvalidData` if the input ended in the middle of a codepoint.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.partial.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "incomplete UTF-8 sequence at end of input"));
+        }
+        let roman = self.romanizer.finish();
+        self.inner.write_all(roman.as_bytes())?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for RomanizingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.partial.extend_from_slice(buf);
+        let text = match take_utf8(&mut self.partial) {
+            Ok(text) => text,
+            Err(e) => {
+                self.partial.truncate(self.partial.len() - buf.len());
+                return Err(e);
+            }
+        };
+        let roman = self.romanizer.push(&text);
+        self.inner.write_all(roman.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    /// Flushes the inner writer. The last word stays held back, since the
+    /// next write may still change it.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A reader that romanizes the UTF-8 text of an inner reader on the fly, in
+/// constant memory.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use jamo::batch::{CorpusOptions, RomanizingReader};
+/// let mut reader = RomanizingReader::new("좋아요\n안녕".as_bytes(), CorpusOptions::default());
+/// let mut roman = String::new();
+/// reader.read_to_string(&mut roman).unwrap();
+/// assert_eq!("joayo\nannyeong", roman);
+/// ```
+pub struct RomanizingReader<R: Read> {
+    inner: R,
+    romanizer: StreamingRomanizer,
+    partial: Vec<u8>,
+    output: Vec<u8>,
+    position: usize,
+    done: bool,
+}
+
+impl<R: Read> RomanizingReader<R> {
+    pub fn new(inner: R, options: CorpusOptions) -> Self {
+        Self {
+            inner,
+            romanizer: StreamingRomanizer::new(options),
+            partial: vec![],
+            output: vec![],
+            position: 0,
+            done: false,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for RomanizingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.output.len() && !self.done {
+            let mut chunk = [0; 8192];
+            let n = self.inner.read(&mut chunk)?;
+            let roman = if n == 0 {
+                self.done = true;
+                if !self.partial.is_empty() {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "incomplete UTF-8 sequence at end of input"));
+                }
+                self.romanizer.finish()
+            } else {
+                self.partial.extend_from_slice(&chunk[..n]);
+                self.romanizer.push(&take_utf8(&mut self.partial)?)
+            };
+            self.output = roman.into_bytes();
+            self.position = 0;
+        }
+        let n = buf.len().min(self.output.len() - self.position);
+        buf[..n].copy_from_slice(&self.output[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Removes and returns the longest valid UTF-8 prefix of `bytes`, leaving an
+/// incomplete trailing codepoint behind.
+fn take_utf8(bytes: &mut Vec<u8>) -> io::Result<String> {
+    let valid = match std::str::from_utf8(bytes) {
+        Ok(text) => text.len(),
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+    };
+    let rest = bytes.split_off(valid);
+    String::from_utf8(std::mem::replace(bytes, rest)).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Processes a stream of lines, yielding their outputs without blocking, for
+/// async services.
+///
+/// # Examples
+///
+/// ```
+/// use std::pin::Pin;
+/// use std::sync::Arc;
+/// use std::task::{Context, Poll, Wake, Waker};
+/// use futures_core::Stream;
+/// use jamo::batch::{romanize_stream, CorpusOptions};
+///
+/// struct Lines(Vec<String>);
+/// impl Stream for Lines {
+///     type Item = String;
+///     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<String>> {
+///         Poll::Ready(self.0.pop())
+///     }
+/// }
+///
+/// struct Noop;
+/// impl Wake for Noop {
+///     fn wake(self: Arc<Self>) {}
+/// }
+///
+/// let mut outputs = romanize_stream(Lines(vec!["좋아요".to_string()]), CorpusOptions::default());
+/// let waker = Waker::from(Arc::new(Noop));
+/// let mut cx = Context::from_waker(&waker);
+/// match Pin::new(&mut outputs).poll_next(&mut cx) {
+///     Poll::Ready(Some(output)) => assert_eq!("joayo", output.roman),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub fn romanize_stream<S>(lines: S, options: CorpusOptions) -> RomanizeStream<S>
+    where S: futures_core::Stream<Item = String> + Unpin {
+    RomanizeStream { lines, options, index: 0 }
+}
+
+#[cfg(feature = "async")]
+pub struct RomanizeStream<S> {
+    lines: S,
+    options: CorpusOptions,
+    index: usize,
+}
+
+#[cfg(feature = "async")]
+impl<S> futures_core::Stream for RomanizeStream<S>
+    where S: futures_core::Stream<Item = String> + Unpin {
+    type Item = Output;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>)
+                 -> std::task::Poll<Option<Output>> {
+        let this = &mut *self;
+        if this.options.is_cancelled() {
+            return std::task::Poll::Ready(None);
+        }
+        std::pin::Pin::new(&mut this.lines).poll_next(cx).map(|line| line.map(|line| {
+            let output = Output::new(this.index, line, &this.options);
+            this.index += 1;
+            output
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lines.size_hint()
+    }
+}
@@ -0,0 +1,343 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::hangul::{LEAD_COMPAT, TAIL_COMPAT};
+
+const LEAD_COUNT: u32 = 19;
+const VOWEL_COUNT: u32 = 21;
+const TAIL_COUNT: u32 = 28;
+const COMPAT_CONSONANTS: u32 = 30;
+
+/// A Unicode block holding Hangul jamo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Block {
+    /// Conjoining jamo, U+1100..=U+11FF.
+    Conjoining,
+    /// Compatibility jamo, U+3130..=U+318F.
+    Compatibility,
+    /// Halfwidth jamo, U+FFA0..=U+FFDC.
+    Halfwidth,
+    /// Archaic lead clusters, U+A960..=U+A97F.
+    ExtendedA,
+    /// Archaic vowels and tails, U+D7B0..=U+D7FF.
+    ExtendedB,
+}
+
+impl Block {
+    /// Returns the Unicode name of the block.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Block::Conjoining => "Hangul Jamo",
+            Block::Compatibility => "Hangul Compatibility Jamo",
+            Block::Halfwidth => "Halfwidth and Fullwidth Forms",
+            Block::ExtendedA => "Hangul Jamo Extended-A",
+            Block::ExtendedB => "Hangul Jamo Extended-B",
+        }
+    }
+}
+
+/// Where a jamo sits in a syllable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Position {
+    Lead,
+    Vowel,
+    Tail,
+}
+
+/// Returns the block of `c`, or `None` if it is not a jamo.
+pub fn block(c: char) -> Option<Block> {
+    match c {
+        '\u{1100}'..='\u{11ff}' => Some(Block::Conjoining),
+        '\u{3130}'..='\u{318f}' => Some(Block::Compatibility),
+        '\u{ffa0}'..='\u{ffdc}' => Some(Block::Halfwidth),
+        '\u{a960}'..='\u{a97f}' => Some(Block::ExtendedA),
+        '\u{d7b0}'..='\u{d7ff}' => Some(Block::ExtendedB),
+        _ => None,
+    }
+}
+
+/// Returns whether `c` is a Hangul Filler, a blank placeholder for a missing
+/// lead or vowel.
+pub(crate) fn is_filler(c: char) -> bool {
+    matches!(c, '\u{115f}' | '\u{1160}' | '\u{3164}' | '\u{ffa0}')
+}
+
+/// Returns whether `c` is a precomposed syllable or a jamo other than a filler.
+pub(crate) fn is_hangul(c: char) -> bool {
+    crate::hangul::decompose(c).is_some() || block(c).is_some() && !is_filler(c)
+}
+
+/// Returns the index of `c` in the crate's lead, vowel or tail table, as in
+/// [`Jamo::index`](crate::hangul::Jamo::index), for a jamo from any block.
+///
+/// Compatibility and halfwidth consonants stand for both a lead and a tail.
+/// The archaic jamo of the conjoining and extended blocks have no modern
+/// index and give `None`.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::blocks::{index, Position};
+/// for c in ['\u{1112}', 'ㅎ', '\u{ffbe}'] {
+///     assert_eq!(Some(18), index(c, Position::Lead));
+/// }
+/// assert_eq!(Some(27), index('ㅎ', Position::Tail));
+/// assert_eq!(Some(0), index('\u{ffc2}', Position::Vowel));
+/// assert_eq!(None, index('ㄸ', Position::Tail));
+/// assert_eq!(None, index('\u{a960}', Position::Lead));
+/// ```
+pub fn index(c: char, position: Position) -> Option<usize> {
+    let conjoining = match position {
+        Position::Lead => offset(c, 0x1100, LEAD_COUNT),
+        Position::Vowel => offset(c, 0x1161, VOWEL_COUNT),
+        Position::Tail => offset(c, 0x11a7, TAIL_COUNT).filter(|&i| i > 0),
+    };
+    if conjoining.is_some() {
+        return conjoining;
+    }
+    let compat = to_compatibility(c).filter(|_| block(c) != Some(Block::Conjoining))?;
+    match position {
+        Position::Lead => LEAD_COMPAT.iter().position(|&l| l == compat),
+        Position::Vowel => offset(compat, 0x314f, VOWEL_COUNT),
+        Position::Tail => TAIL_COMPAT.iter().skip(1).position(|&t| t == compat).map(|i| i + 1),
+    }
+}
+
+/// Returns the conjoining jamo for `c` in `position`, from any block.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::blocks::{to_conjoining, Position};
+/// assert_eq!(Some('\u{11ab}'), to_conjoining('ㄴ', Position::Tail));
+/// assert_eq!(Some('\u{1102}'), to_conjoining('\u{11ab}', Position::Lead));
+/// ```
+pub fn to_conjoining(c: char, position: Position) -> Option<char> {
+    let c = to_compatibility(c).unwrap_or(c);
+    let base = match position {
+        Position::Lead => 0x1100,
+        Position::Vowel => 0x1161,
+        Position::Tail => 0x11a7,
+    };
+    char::from_u32(base + index(c, position)? as u32)
+}
+
+/// Returns the compatibility jamo for a modern jamo from any block.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::blocks::to_compatibility;
+/// assert_eq!(Some('ㄱ'), to_compatibility('\u{1100}'));
+/// assert_eq!(Some('ㄳ'), to_compatibility('\u{11aa}'));
+/// assert_eq!(Some('ㅢ'), to_compatibility('\u{ffdb}'));
+/// assert_eq!(None, to_compatibility('가'));
+/// ```
+pub fn to_compatibility(c: char) -> Option<char> {
+    if let Some(i) = offset(c, 0x1100, LEAD_COUNT) {
+        return Some(LEAD_COMPAT[i]);
+    }
+    if let Some(i) = offset(c, 0x1161, VOWEL_COUNT) {
+        return char::from_u32(0x314f + i as u32);
+    }
+    if let Some(i) = offset(c, 0x11a8, TAIL_COUNT - 1) {
+        return Some(TAIL_COMPAT[i + 1]);
+    }
+    if offset(c, 0x3131, COMPAT_CONSONANTS + VOWEL_COUNT).is_some() {
+        return Some(c);
+    }
+    let i = match c as u32 {
+        code @ 0xffa1..=0xffbe => code - 0xffa1,
+        code @ 0xffc2..=0xffdc if (code - 0xffc2) % 8 < 6 => {
+            COMPAT_CONSONANTS + (code - 0xffc2) / 8 * 6 + (code - 0xffc2) % 8
+        }
+        _ => return None,
+    };
+    char::from_u32(0x3131 + i)
+}
+
+/// Returns the halfwidth jamo for a modern jamo from any block.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::blocks::to_halfwidth;
+/// assert_eq!(Some('\u{ffa1}'), to_halfwidth('ㄱ'));
+/// assert_eq!(Some('\u{ffdc}'), to_halfwidth('\u{1175}'));
+/// ```
+pub fn to_halfwidth(c: char) -> Option<char> {
+    let i = to_compatibility(c)? as u32 - 0x3131;
+    let code = match i.checked_sub(COMPAT_CONSONANTS) {
+        None => 0xffa1 + i,
+        Some(v) => 0xffc2 + v / 6 * 8 + v % 6,
+    };
+    char::from_u32(code)
+}
+
+/// What a lead, vowel and tail compose to, from [`compose_checked`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Composition {
+    /// A precomposed modern syllable.
+    Syllable(char),
+    /// A syllable with Old Hangul jamo, which has no precomposed form and is
+    /// written as its conjoining sequence.
+    OldHangul(String),
+    /// Something other than a lead, vowel and tail in one of the positions.
+    Invalid,
+}
+
+/// Composes a lead, vowel and optional tail given in any block, including
+/// the Old Hangul jamo of the conjoining and extended blocks.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::blocks::{compose_checked, Composition};
+/// assert_eq!(Composition::Syllable('한'), compose_checked('ㅎ', '\u{1161}', Some('ㄴ')));
+/// // ᄒ with arae-a.
+/// assert_eq!(Composition::OldHangul("\u{1112}\u{119e}".to_string()), compose_checked('\u{1112}', 'ㆍ', None));
+/// assert_eq!(Composition::Invalid, compose_checked('ㅏ', 'ㅏ', None));
+/// ```
+pub fn compose_checked(lead: char, vowel: char, tail: Option<char>) -> Composition {
+    let modern_tail = tail.map_or(Some(0), |t| index(t, Position::Tail));
+    if let (Some(l), Some(v), Some(t)) = (index(lead, Position::Lead), index(vowel, Position::Vowel), modern_tail) {
+        return compose_indices(l, v, t).map_or(Composition::Invalid, Composition::Syllable);
+    }
+    let conjoining = |c: char, position| to_conjoining(c, position).or_else(|| archaic(c, position));
+    let tail = tail.map(|t| conjoining(t, Position::Tail));
+    match (conjoining(lead, Position::Lead), conjoining(vowel, Position::Vowel), tail) {
+        (Some(lead), Some(vowel), None) => Composition::OldHangul([lead, vowel].iter().collect()),
+        (Some(lead), Some(vowel), Some(Some(tail))) => Composition::OldHangul([lead, vowel, tail].iter().collect()),
+        _ => Composition::Invalid,
+    }
+}
+
+/// Why [`compose`] or [`compose_indices`] could not build a syllable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComposeError {
+    /// The char is not one of the 19 modern leads in any block.
+    NotALead(char),
+    /// The char is not one of the 21 modern vowels in any block.
+    NotAVowel(char),
+    /// The char is not one of the 27 modern tails in any block.
+    NotATail(char),
+    /// The index is past the end of the jamo table for its position.
+    IndexOutOfRange(Position, usize),
+}
+
+impl fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ComposeError::NotALead(c) => write!(f, "{:?} is not a lead consonant", c),
+            ComposeError::NotAVowel(c) => write!(f, "{:?} is not a vowel", c),
+            ComposeError::NotATail(c) => write!(f, "{:?} is not a tail consonant", c),
+            ComposeError::IndexOutOfRange(position, i) => write!(f, "{:?} index {} is out of range", position, i),
+        }
+    }
+}
+
+impl Error for ComposeError {}
+
+/// Returns the precomposed syllable of a modern lead, vowel and optional
+/// tail, given as jamo from any block.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::blocks::{compose, ComposeError};
+/// assert_eq!(Ok('한'), compose('ㅎ', 'ㅏ', Some('ㄴ')));
+/// assert_eq!(Ok('가'), compose('\u{1100}', '\u{1161}', None));
+/// assert_eq!(Err(ComposeError::NotALead('ㄳ')), compose('ㄳ', 'ㅏ', None));
+/// assert_eq!(Err(ComposeError::NotATail('ㄸ')), compose('ㄱ', 'ㅏ', Some('ㄸ')));
+/// ```
+pub fn compose(lead: char, vowel: char, tail: Option<char>) -> Result<char, ComposeError> {
+    let l = index(lead, Position::Lead).ok_or(ComposeError::NotALead(lead))?;
+    let v = index(vowel, Position::Vowel).ok_or(ComposeError::NotAVowel(vowel))?;
+    let t = match tail {
+        Some(tail) => index(tail, Position::Tail).ok_or(ComposeError::NotATail(tail))?,
+        None => 0,
+    };
+    compose_indices(l, v, t)
+}
+
+/// Returns the precomposed syllable of a lead, vowel and tail given as
+/// indices into the modern jamo tables, with tail 0 for none.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::blocks::{compose_indices, ComposeError, Position};
+/// assert_eq!(Ok('각'), compose_indices(0, 0, 1));
+/// assert_eq!(Err(ComposeError::IndexOutOfRange(Position::Vowel, 21)), compose_indices(0, 21, 0));
+/// ```
+pub fn compose_indices(lead: usize, vowel: usize, tail: usize) -> Result<char, ComposeError> {
+    for (i, position, count) in [(lead, Position::Lead, LEAD_COUNT), (vowel, Position::Vowel, VOWEL_COUNT),
+                                 (tail, Position::Tail, TAIL_COUNT)] {
+        if i >= count as usize {
+            return Err(ComposeError::IndexOutOfRange(position, i));
+        }
+    }
+    char::from_u32(0xac00 + ((lead * 21 + vowel) * 28 + tail) as u32).ok_or(ComposeError::IndexOutOfRange(Position::Lead, lead))
+}
+
+// Returns `c` if it is an Old Hangul conjoining jamo for `position`, mapping
+// the obsolete compatibility jamo to their conjoining forms.
+fn archaic(c: char, position: Position) -> Option<char> {
+    let c = match (c, position) {
+        ('ㅿ', Position::Lead) => '\u{1140}',
+        ('ㆁ', Position::Lead) => '\u{114c}',
+        ('ㆆ', Position::Lead) => '\u{1159}',
+        ('ㅸ', Position::Lead) => '\u{112b}',
+        ('ㆍ', Position::Vowel) => '\u{119e}',
+        ('ㆎ', Position::Vowel) => '\u{11a1}',
+        ('ㅿ', Position::Tail) => '\u{11eb}',
+        ('ㆁ', Position::Tail) => '\u{11f0}',
+        ('ㆆ', Position::Tail) => '\u{11f9}',
+        _ => c,
+    };
+    let archaic = match position {
+        Position::Lead => ('\u{1113}'..='\u{115e}').contains(&c) || ('\u{a960}'..='\u{a97c}').contains(&c),
+        Position::Vowel => ('\u{1176}'..='\u{11a7}').contains(&c) || ('\u{d7b0}'..='\u{d7c6}').contains(&c),
+        Position::Tail => ('\u{11c3}'..='\u{11ff}').contains(&c) || ('\u{d7cb}'..='\u{d7fb}').contains(&c),
+    };
+    Some(c).filter(|_| archaic)
+}
+
+/// Rewrites every modern halfwidth jamo in `text`, and every conjoining jamo
+/// outside a lead, vowel and optional tail sequence, as compatibility jamo.
+/// Syllables and archaic jamo are left as they are, and conjoining sequences
+/// are left for [`KoreanSentence::repaired`](crate::hangul::KoreanSentence::repaired).
+///
+/// # Examples
+///
+/// ```
+/// use jamo::blocks::normalize;
+/// assert_eq!("ㅋㅋ 한", normalize("\u{ffbb}\u{ffbb} 한"));
+/// assert_eq!("ㄱㄱ", normalize("\u{1100}\u{1100}"));
+/// assert_eq!("\u{1100}\u{1161}", normalize("\u{1100}\u{1161}"));
+/// ```
+pub fn normalize(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<char>>();
+    let at = |i: Option<usize>, position| {
+        i.and_then(|i| chars.get(i)).is_some_and(|&c| is_conjoining(c, position))
+    };
+    chars.iter().enumerate().map(|(i, &c)| {
+        let (previous, next) = (i.checked_sub(1), Some(i + 1));
+        let in_syllable = is_conjoining(c, Position::Lead) && at(next, Position::Vowel)
+            || is_conjoining(c, Position::Vowel) && at(previous, Position::Lead)
+            || is_conjoining(c, Position::Tail) && at(previous, Position::Vowel);
+        match block(c) {
+            Some(Block::Halfwidth) => to_compatibility(c).unwrap_or(c),
+            Some(Block::Conjoining) if !in_syllable => to_compatibility(c).unwrap_or(c),
+            _ => c,
+        }
+    }).collect()
+}
+
+fn is_conjoining(c: char, position: Position) -> bool {
+    block(c) == Some(Block::Conjoining) && index(c, position).is_some()
+}
+
+fn offset(c: char, base: u32, len: u32) -> Option<usize> {
+    (c as u32).checked_sub(base).filter(|&i| i < len).map(|i| i as usize)
+}
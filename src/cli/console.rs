@@ -0,0 +1,132 @@
+use std::io::{self, Write};
+
+/// Returns the standard output of the CLI.
+///
+/// On a Windows console, text is written as UTF-16 with `WriteConsoleW`, so
+/// Hangul shows whatever the console code page, and bytes that are not valid
+/// UTF-8 are replaced with U+FFFD instead of failing the write. A multi-byte
+/// sequence split across writes is held back until it is complete. Anywhere
+/// else, and when output is redirected, bytes go to `io::stdout` unchanged.
+pub fn stdout() -> Stdout {
+    Stdout {
+        inner: io::stdout(),
+        #[cfg(windows)]
+        console: windows::Console::stdout(),
+    }
+}
+
+pub struct Stdout {
+    inner: io::Stdout,
+    #[cfg(windows)]
+    console: Option<windows::Console>,
+}
+
+impl Write for Stdout {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[cfg(windows)]
+        if let Some(console) = &mut self.console {
+            return console.write(buf);
+        }
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        #[cfg(windows)]
+        if let Some(console) = &mut self.console {
+            return console.flush();
+        }
+        self.inner.flush()
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::ffi::c_void;
+    use std::io;
+    use std::os::windows::io::{AsRawHandle, RawHandle};
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetConsoleMode(console: RawHandle, mode: *mut u32) -> i32;
+        fn WriteConsoleW(console: RawHandle, buffer: *const u16, length: u32,
+                         written: *mut u32, reserved: *mut c_void) -> i32;
+    }
+
+    pub struct Console {
+        handle: RawHandle,
+        // The start of a UTF-8 sequence whose remaining bytes are yet to come.
+        pending: Vec<u8>,
+    }
+
+    impl Console {
+        /// Returns standard output if it is a console.
+        pub fn stdout() -> Option<Self> {
+            let handle = io::stdout().as_raw_handle();
+            let mut mode = 0;
+            if handle.is_null() || unsafe { GetConsoleMode(handle, &mut mode) } == 0 {
+                return None;
+            }
+            Some(Console { handle, pending: vec![] })
+        }
+
+        pub fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.pending.extend_from_slice(buf);
+            let (text, used) = decode(&self.pending);
+            self.pending.drain(..used);
+            self.write_utf16(&text.encode_utf16().collect::<Vec<u16>>())?;
+            Ok(buf.len())
+        }
+
+        // Every complete char has been written by then; a sequence still
+        // pending may yet be finished by the next write.
+        pub fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn write_utf16(&self, mut units: &[u16]) -> io::Result<()> {
+            while !units.is_empty() {
+                // Stay well below the 64 KiB the console accepts per call,
+                // without splitting a surrogate pair.
+                let mut length = units.len().min(8192);
+                if length < units.len() && (0xd800..0xdc00).contains(&units[length - 1]) {
+                    length -= 1;
+                }
+                let mut written = 0;
+                let ok = unsafe {
+                    WriteConsoleW(self.handle, units.as_ptr(), length as u32, &mut written, std::ptr::null_mut())
+                };
+                if ok == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                units = &units[(written as usize).max(1).min(units.len())..];
+            }
+            Ok(())
+        }
+    }
+
+    /// Decodes `bytes`, replacing invalid sequences with U+FFFD but leaving
+    /// an incomplete one at the end, and returns the text and the bytes used.
+    fn decode(bytes: &[u8]) -> (String, usize) {
+        let mut text = String::with_capacity(bytes.len());
+        let mut used = 0;
+        loop {
+            match std::str::from_utf8(&bytes[used..]) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    return (text, bytes.len());
+                }
+                Err(e) => {
+                    let valid = used + e.valid_up_to();
+                    text.push_str(&String::from_utf8_lossy(&bytes[used..valid]));
+                    match e.error_len() {
+                        Some(invalid) => {
+                            text.push('\u{fffd}');
+                            used = valid + invalid;
+                        }
+                        None => return (text, valid),
+                    }
+                }
+            }
+        }
+    }
+}
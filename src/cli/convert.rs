@@ -0,0 +1,121 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use jamo::batch::{self, CorpusOptions};
+
+use super::{Format, RuleChoice};
+
+#[derive(Default)]
+struct Summary {
+    files: usize,
+    lines: usize,
+}
+
+/// Runs `jamo convert --in-dir <dir> --out-dir <dir>`, romanizing every file
+/// under the input directory into the same relative path under the output
+/// directory. Files are spread over the available threads. With `--format
+/// json` each line is written as one JSON object instead.
+pub fn run(args: &[String], rules: RuleChoice, format: Format, strict: bool) -> io::Result<()> {
+    let (mut in_dir, mut out_dir) = (None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--in-dir" => in_dir = args.next().map(PathBuf::from),
+            "--out-dir" => out_dir = args.next().map(PathBuf::from),
+            _ => return Err(invalid(format!("unexpected argument: {}", arg))),
+        }
+    }
+    let (in_dir, out_dir) = match (in_dir, out_dir) {
+        (Some(in_dir), Some(out_dir)) => (in_dir, out_dir),
+        _ => return Err(invalid("convert needs --in-dir and --out-dir".to_string())),
+    };
+    let mut files = vec![];
+    walk(&in_dir, &mut files)?;
+
+    let options = rules.corpus_options();
+    let next = AtomicUsize::new(0);
+    let summary = Mutex::new(Summary::default());
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(files.len().max(1));
+    thread::scope(|scope| {
+        let handles = (0..workers).map(|_| scope.spawn(|| -> io::Result<()> {
+            while let Some(file) = files.get(next.fetch_add(1, Ordering::Relaxed)) {
+                let relative = file.strip_prefix(&in_dir).unwrap_or(file);
+                let to = out_dir.join(relative);
+                let lines = match format {
+                    Format::Json => convert_file_json(file, &to, &rules, strict)?,
+                    Format::Blocks | Format::Table => convert_file(file, &to, &options, strict)?,
+                };
+                let mut summary = summary.lock().unwrap();
+                summary.files += 1;
+                summary.lines += lines;
+            }
+            Ok(())
+        })).collect::<Vec<_>>();
+        handles.into_iter().try_for_each(|h| h.join().unwrap())
+    })?;
+
+    let summary = summary.into_inner().unwrap();
+    writeln!(super::console::stdout(), "{} files, {} lines", summary.files, summary.lines)
+}
+
+/// Collects the files under `dir`. Symbolic links are not followed into
+/// directories, so a link loop cannot recurse forever; links to files are
+/// converted like files.
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let (file_type, path) = (entry.file_type()?, entry.path());
+        if file_type.is_dir() {
+            walk(&path, files)?;
+        } else if !(file_type.is_symlink() && path.is_dir()) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Writes the romanization of `from` to `to`, returning the number of lines
+/// processed. Lines that are not valid UTF-8 are converted lossily with a
+/// warning, or fail the file when `strict` is set.
+fn convert_file(from: &Path, to: &Path, options: &CorpusOptions, strict: bool) -> io::Result<usize> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let throughput = batch::convert_file_with(from, to, 1, options)?;
+    if throughput.lossy_lines > 0 {
+        super::invalid_utf8(&format_args!("{} ({} lines)", from.display(), throughput.lossy_lines), strict)?;
+    }
+    Ok(throughput.lines)
+}
+
+/// Like [`convert_file`], writing each line in the schema of `jamo::json`.
+fn convert_file_json(from: &Path, to: &Path, rules: &RuleChoice, strict: bool) -> io::Result<usize> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut input = BufReader::new(fs::File::open(from)?);
+    let mut output = BufWriter::new(fs::File::create(to)?);
+    let mut line = vec![];
+    let mut lines = 0;
+    while input.read_until(b'\n', &mut line)? > 0 {
+        lines += 1;
+        if line.ends_with(b"\n") {
+            line.pop();
+            if line.ends_with(b"\r") {
+                line.pop();
+            }
+        }
+        let text = super::decode(std::mem::take(&mut line), &format_args!("{}:{}", from.display(), lines), strict)?;
+        writeln!(output, "{}", rules.to_json(&text))?;
+    }
+    output.flush()?;
+    Ok(lines)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
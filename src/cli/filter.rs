@@ -0,0 +1,50 @@
+use std::io::{self, BufRead, BufWriter, Write};
+
+use jamo::batch::Output;
+
+use super::{Format, RuleChoice};
+
+/// Runs `jamo filter [-0|--null] [--line-buffered]`, romanizing each record
+/// of standard input to standard output.
+///
+/// Records end in a newline, or in a NUL byte with `--null` for use with
+/// `find -print0` and `xargs -0`. Output is block-buffered unless
+/// `--line-buffered` asks for a flush after every record. Each record is
+/// romanized, or with `--format json` written as one JSON object.
+pub fn run(args: &[String], rules: RuleChoice, format: Format, strict: bool) -> io::Result<()> {
+    let (mut delimiter, mut line_buffered) = (b'\n', false);
+    for arg in args {
+        match arg.as_str() {
+            "-0" | "--null" => delimiter = b'\0',
+            "--line-buffered" => line_buffered = true,
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("unexpected argument: {}", arg))),
+        }
+    }
+    let options = rules.corpus_options();
+    let render = |index, source: String| match format {
+        Format::Json => rules.to_json(&source),
+        Format::Blocks | Format::Table => Output::new(index, source, &options).roman,
+    };
+    filter(io::stdin().lock(), BufWriter::new(super::console::stdout()), delimiter, line_buffered, strict, render)
+}
+
+fn filter<R: BufRead, W: Write>(mut input: R, mut output: W, delimiter: u8, line_buffered: bool,
+                                strict: bool, render: impl Fn(usize, String) -> String) -> io::Result<()> {
+    let mut record = vec![];
+    for index in 0.. {
+        record.clear();
+        if input.read_until(delimiter, &mut record)? == 0 {
+            break;
+        }
+        if record.last() == Some(&delimiter) {
+            record.pop();
+        }
+        let source = super::decode(record.clone(), &format_args!("record {}", index + 1), strict)?;
+        output.write_all(render(index, source).as_bytes())?;
+        output.write_all(&[delimiter])?;
+        if line_buffered {
+            output.flush()?;
+        }
+    }
+    output.flush()
+}
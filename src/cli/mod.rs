@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::io;
+
+use jamo::batch::CorpusOptions;
+use jamo::hangul::KoreanSentence;
+use jamo::rules::{self, Rule, RuleProfile};
+
+pub mod console;
+pub mod convert;
+pub mod filter;
+pub mod repl;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod watch;
+
+#[cfg(feature = "serve")]
+pub const USAGE: &str = "\
+usage: jamo [--rules none|standard|colloquial|gyeongsang|jeolla|file:<path>]
+            [--format blocks|table|json]
+            [--watch-rules <file>] [--strict-utf8]
+            [repl | convert --in-dir <dir> --out-dir <dir>
+             | filter [-0|--null] [--line-buffered] | serve [--addr <host:port>] | <text>...]";
+#[cfg(not(feature = "serve"))]
+pub const USAGE: &str = "\
+usage: jamo [--rules none|standard|colloquial|gyeongsang|jeolla|file:<path>]
+            [--format blocks|table|json]
+            [--watch-rules <file>] [--strict-utf8]
+            [repl | convert --in-dir <dir> --out-dir <dir>
+             | filter [-0|--null] [--line-buffered] | <text>...]";
+
+/// How `jamo <text>...` prints its result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Blocks,
+    Table,
+    /// The rule-applied sentence in the schema of `jamo::json`.
+    Json,
+}
+
+/// The rules picked with `--rules` or the repl's `:rules`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleChoice {
+    /// A built-in profile, with its reductions and dialect shifts.
+    Profile(RuleProfile),
+    /// Rules alone: none, or those of a rule file.
+    Rules(Cow<'static, [Rule]>),
+}
+
+impl RuleChoice {
+    /// Returns the choice called `name`: "none" or a [`RuleProfile`] name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(RuleChoice::Rules(Cow::Borrowed(&[]))),
+            name => RuleProfile::from_name(name).map(RuleChoice::Profile),
+        }
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        match self {
+            RuleChoice::Profile(profile) => profile.rules(),
+            RuleChoice::Rules(rules) => rules,
+        }
+    }
+
+    /// Returns `sentence` set up to apply the chosen rules.
+    pub fn apply_to(&self, sentence: KoreanSentence) -> KoreanSentence {
+        match self {
+            RuleChoice::Profile(profile) => sentence.with_profile(*profile),
+            RuleChoice::Rules(rules) => sentence.with_rules(rules.clone()),
+        }
+    }
+
+    /// Returns `text` after the chosen rules, in the schema of `jamo::json`.
+    pub fn to_json(&self, text: &str) -> String {
+        self.apply_to(KoreanSentence::new(text)).applied().to_json()
+    }
+
+    pub fn corpus_options(&self) -> CorpusOptions {
+        match self {
+            RuleChoice::Profile(profile) => CorpusOptions { profile: Some(*profile), ..CorpusOptions::default() },
+            RuleChoice::Rules(rules) => CorpusOptions { rules: rules.clone(), ..CorpusOptions::default() },
+        }
+    }
+}
+
+/// Parses the value of `--rules`: a built-in profile name or `file:` and the
+/// path of a rule file, in the table format of `rules::parse_rule_tables` if
+/// it ends in `.toml` and the line format otherwise.
+pub fn rules_arg(value: &str) -> io::Result<RuleChoice> {
+    match value.strip_prefix("file:") {
+        Some(path) if path.ends_with(".toml") => return rules::load_rule_tables(path).map(|r| RuleChoice::Rules(Cow::Owned(r))),
+        Some(path) => return rules::load_rules(path).map(|r| RuleChoice::Rules(Cow::Owned(r))),
+        None => {}
+    }
+    RuleChoice::from_name(value).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("unknown rule profile: {}", value))
+    })
+}
+
+/// Decodes one input record, see [`invalid_utf8`].
+pub fn decode(bytes: Vec<u8>, record: &dyn Display, strict: bool) -> io::Result<String> {
+    String::from_utf8(bytes).or_else(|e| {
+        invalid_utf8(record, strict)?;
+        Ok(String::from_utf8_lossy(e.as_bytes()).into_owned())
+    })
+}
+
+/// Handles a record that is not valid UTF-8. It is replaced with U+FFFD and
+/// reported on stderr, or fails with `InvalidData` when `strict` is set, so a
+/// bad record never stops a batch job partway through unless asked to.
+pub fn invalid_utf8(record: &dyn Display, strict: bool) -> io::Result<()> {
+    if strict {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{}: invalid UTF-8", record)));
+    }
+    eprintln!("jamo: {}: invalid UTF-8 replaced", record);
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use std::io::{self, BufRead, Write};
+
+use jamo::hangul::KoreanSentence;
+use jamo::roman::{romanize, RomanizationSystem};
+
+use super::RuleChoice;
+
+const HELP: &str = "\
+:rules on|off            apply pronunciation rules before romanizing
+:rules <profile>         use the none, standard, colloquial, gyeongsang or jeolla rules
+:system jamo|rr|mr|yale  romanize letter by letter, Revised, McCune-Reischauer or Middle Korean Yale
+:help                    show this help
+:quit                    exit";
+
+struct Settings {
+    rules: bool,
+    profile: RuleChoice,
+    system: RomanizationSystem,
+}
+
+/// Reads lines from `input` and prints their forms to `output` until `:quit`
+/// or end of input.
+pub fn run<R: BufRead, W: Write>(input: R, mut output: W, profile: RuleChoice,
+                                 strict: bool) -> io::Result<()> {
+    let mut settings = Settings { rules: true, profile, system: RomanizationSystem::Revised };
+    write!(output, "> ")?;
+    output.flush()?;
+    for (i, line) in input.split(b'\n').enumerate() {
+        let line = super::decode(line?, &format_args!("line {}", i + 1), strict)?;
+        let line = line.trim();
+        if line == ":quit" || line == ":q" {
+            return Ok(());
+        }
+        if line.starts_with(':') {
+            writeln!(output, "{}", command(line, &mut settings))?;
+        } else if !line.is_empty() {
+            display(line, &settings, &mut output)?;
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+    writeln!(output)
+}
+
+fn command(line: &str, settings: &mut Settings) -> String {
+    let mut words = line.split_whitespace();
+    match (words.next(), words.next()) {
+        (Some(":rules"), Some("on")) => settings.rules = true,
+        (Some(":rules"), Some("off")) => settings.rules = false,
+        (Some(":rules"), Some(name)) => match RuleChoice::from_name(name) {
+            Some(profile) => settings.profile = profile,
+            None => return format!("unknown rule profile: {}\n{}", name, HELP),
+        },
+        (Some(":system"), Some("jamo")) => settings.system = RomanizationSystem::Jamo,
+        (Some(":system"), Some("rr")) => settings.system = RomanizationSystem::Revised,
+        (Some(":system"), Some("mr")) => settings.system = RomanizationSystem::McCuneReischauer,
+        (Some(":system"), Some("yale")) => settings.system = RomanizationSystem::Yale,
+        (Some(":help"), _) => return HELP.to_string(),
+        _ => return format!("unknown command: {}\n{}", line, HELP),
+    }
+    format!("rules {}, system {:?}", if settings.rules { "on" } else { "off" }, settings.system)
+}
+
+fn display<W: Write>(line: &str, settings: &Settings, output: &mut W) -> io::Result<()> {
+    let sentence = settings.profile.apply_to(KoreanSentence::new(line));
+    let romanized = if settings.rules { sentence.applied() } else { sentence.clone() };
+    writeln!(output, "hangul      {}", line)?;
+    writeln!(output, "jamo        {}", sentence.jamo())?;
+    writeln!(output, "roman       {}", romanize(&romanized, settings.system))?;
+    writeln!(output, "pronounced  {}", sentence.pronounced())
+}
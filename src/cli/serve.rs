@@ -0,0 +1,108 @@
+use std::io::{self, Read};
+use std::thread;
+
+use jamo::hangul::KoreanSentence;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::RuleChoice;
+
+/// Runs `jamo serve [--addr <host:port>]`, answering HTTP requests until the
+/// process is stopped:
+///
+/// - `/romanize` returns the romanization after rules,
+/// - `/pronounce` the pronounced Hangul,
+/// - `/decompose` the letters as JSON in the schema of `jamo::json`.
+///
+/// The text is the `text` query parameter of a GET or the body of a POST of
+/// at most [`MAX_BODY`] bytes. Requests are answered by one worker thread per
+/// CPU, so a slow client does not hold up the others.
+pub fn run(args: &[String], rules: RuleChoice) -> io::Result<()> {
+    let mut addr = "127.0.0.1:8080".to_string();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--addr", Some(value)) => addr = value.clone(),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("unexpected argument: {}", arg))),
+        }
+    }
+    let server = Server::http(&addr).map_err(io::Error::other)?;
+    eprintln!("jamo: listening on http://{}", addr);
+    let workers = thread::available_parallelism().map_or(4, |n| n.get());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    if let Err(e) = respond(request, &rules) {
+                        eprintln!("jamo: {}", e);
+                    }
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+/// The largest POST body answered; larger ones get 413.
+pub const MAX_BODY: u64 = 1 << 20;
+
+fn respond(mut request: Request, rules: &RuleChoice) -> io::Result<()> {
+    let (path, query) = match request.url().split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (request.url().to_string(), None),
+    };
+    let text = match request.method() {
+        Method::Get => query.as_deref().and_then(|q| parameter(q, "text")),
+        Method::Post => {
+            let mut body = vec![];
+            request.as_reader().take(MAX_BODY + 1).read_to_end(&mut body)?;
+            if body.len() as u64 > MAX_BODY {
+                return request.respond(Response::from_string("body too large\n").with_status_code(413));
+            }
+            String::from_utf8(body).ok()
+        }
+        _ => return request.respond(Response::from_string("method not allowed\n").with_status_code(405)),
+    };
+    let text = match text {
+        Some(text) => text,
+        None => return request.respond(Response::from_string("missing text\n").with_status_code(400)),
+    };
+    let sentence = rules.apply_to(KoreanSentence::new(&text));
+    let (body, content_type) = match path.as_str() {
+        "/romanize" => (sentence.applied().roman(), "text/plain; charset=utf-8"),
+        "/pronounce" => (sentence.pronounced(), "text/plain; charset=utf-8"),
+        "/decompose" => (sentence.to_json(), "application/json"),
+        _ => return request.respond(Response::from_string("not found\n").with_status_code(404)),
+    };
+    let header = Header::from_bytes("Content-Type", content_type).expect("valid header");
+    request.respond(Response::from_string(body).with_header(header))
+}
+
+/// Returns the percent-decoded value of `name` in a query string.
+fn parameter(query: &str, name: &str) -> Option<String> {
+    query.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| percent_decode(value))
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'+', _) => decoded.push(b' '),
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 2;
+            }
+            (b, _) => decoded.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,45 @@
+use std::fs;
+use std::io;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use super::RuleChoice;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Runs `process` with the rules in the file at `path`, then again each time
+/// the file's modification time changes, until interrupted.
+///
+/// A rule file that fails to load or a run that fails is reported and the
+/// watch goes on, so a typo mid-edit does not end the session.
+pub fn run<F>(path: &str, mut process: F) -> io::Result<()>
+    where F: FnMut(RuleChoice) -> io::Result<()> {
+    let value = format!("file:{}", path);
+    let mut last = modified(path)?;
+    loop {
+        match super::rules_arg(&value) {
+            Ok(rules) => {
+                eprintln!("jamo: loaded {} rules from {}", rules.rules().len(), path);
+                if let Err(e) = process(rules) {
+                    eprintln!("jamo: {}", e);
+                }
+            }
+            Err(e) => eprintln!("jamo: {}: {}", path, e),
+        }
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            // The file may briefly vanish while an editor replaces it.
+            match modified(path) {
+                Ok(time) if time != last => {
+                    last = time;
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn modified(path: &str) -> io::Result<SystemTime> {
+    fs::metadata(path)?.modified()
+}
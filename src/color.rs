@@ -0,0 +1,52 @@
+use crate::diff::{edits, Edit};
+use crate::hangul::{KoreanSentence, Letter};
+
+const RESET: &str = "\x1b[0m";
+const CHANGED: &str = "\x1b[1;33m";
+const EXTRA: &str = "\x1b[31m";
+const MISSING: &str = "\x1b[32m";
+
+/// Returns the rule-applied sentence as text with the syllables the rules
+/// changed highlighted in bold yellow.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::color::highlight_changes;
+/// use jamo::hangul::KoreanSentence;
+/// assert_eq!("\x1b[1;33m조\x1b[0m아요", highlight_changes(&KoreanSentence::new("좋아요")));
+/// ```
+pub fn highlight_changes(sentence: &KoreanSentence) -> String {
+    let (applied, changed) = sentence.applied_with_changes();
+    applied.letters().iter().enumerate().map(|(i, letter)| {
+        let text = match letter {
+            Letter::HangulLetter(h) => h.to_string(),
+            Letter::OtherLetter(c) => c.to_string(),
+        };
+        if changed.contains(&i) {
+            format!("{}{}{}", CHANGED, text, RESET)
+        } else {
+            text
+        }
+    }).collect()
+}
+
+/// Returns `actual` with the chars that differ from `expected` highlighted:
+/// extra or wrong chars in red, missing expected chars in green.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::color::highlight_mismatch;
+/// assert_eq!("jo\x1b[32mh\x1b[0mayo", highlight_mismatch("johayo", "joayo"));
+/// assert_eq!("jo\x1b[31mt\x1b[0m\x1b[32mh\x1b[0mayo", highlight_mismatch("johayo", "jotayo"));
+/// ```
+pub fn highlight_mismatch(expected: &str, actual: &str) -> String {
+    let (expected, actual) = (expected.chars().collect::<Vec<char>>(), actual.chars().collect::<Vec<char>>());
+    edits(&actual, &expected).into_iter().map(|edit| match edit {
+        Edit::Keep(c) => c.to_string(),
+        Edit::Delete(c) => format!("{}{}{}", EXTRA, c, RESET),
+        Edit::Insert(c) => format!("{}{}{}", MISSING, c, RESET),
+        Edit::Replace(a, e) => format!("{}{}{}{}{}{}", EXTRA, a, RESET, MISSING, e, RESET),
+    }).collect()
+}
@@ -0,0 +1,80 @@
+use crate::hangul::KoreanSentence;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    Keep(char),
+    Insert(char),
+    Delete(char),
+    Replace(char, char),
+}
+
+impl Edit {
+    pub fn is_keep(&self) -> bool {
+        matches!(self, Self::Keep(_))
+    }
+}
+
+/// Returns the edits turning `a` into `b`, at the granularity of single jamo.
+///
+/// Sentences are compared as basic jamo, with compound vowels and tails and
+/// doubled consonants split, so a grader sees the one missing stroke:
+///
+/// ```
+/// use jamo::diff::{diff, Edit};
+/// use jamo::hangul::KoreanSentence;
+/// let edits = diff(&KoreanSentence::new("잇다"), &KoreanSentence::new("있다"));
+/// let changes = edits.into_iter().filter(|e| !e.is_keep()).collect::<Vec<_>>();
+/// assert_eq!(vec![Edit::Insert('ㅅ')], changes);
+/// ```
+pub fn diff(a: &KoreanSentence, b: &KoreanSentence) -> Vec<Edit> {
+    edits(&atoms(a), &atoms(b))
+}
+
+/// Returns the edits turning the chars `a` into `b`.
+pub(crate) fn edits(a: &[char], b: &[char]) -> Vec<Edit> {
+    // costs[i][j] is the distance between a[i..] and b[j..].
+    let mut costs = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..=a.len()).rev() {
+        for j in (0..=b.len()).rev() {
+            costs[i][j] = if i == a.len() {
+                b.len() - j
+            } else if j == b.len() {
+                a.len() - i
+            } else {
+                let substitution = costs[i + 1][j + 1] + if a[i] == b[j] { 0 } else { 1 };
+                substitution.min(costs[i + 1][j] + 1).min(costs[i][j + 1] + 1)
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut edits = vec![];
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] && costs[i][j] == costs[i + 1][j + 1] {
+            edits.push(Edit::Keep(a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && j < b.len() && costs[i][j] == costs[i + 1][j + 1] + 1 {
+            edits.push(Edit::Replace(a[i], b[j]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && costs[i][j] == costs[i + 1][j] + 1 {
+            edits.push(Edit::Delete(a[i]));
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(b[j]));
+            j += 1;
+        }
+    }
+    edits
+}
+
+fn atoms(s: &KoreanSentence) -> Vec<char> {
+    s.strokes().chars().flat_map(|c| match c {
+        'ㄲ' => vec!['ㄱ', 'ㄱ'],
+        'ㄸ' => vec!['ㄷ', 'ㄷ'],
+        'ㅃ' => vec!['ㅂ', 'ㅂ'],
+        'ㅆ' => vec!['ㅅ', 'ㅅ'],
+        'ㅉ' => vec!['ㅈ', 'ㅈ'],
+        _ => vec![c],
+    }).collect()
+}
@@ -0,0 +1,94 @@
+use crate::blocks;
+
+// Rough relative frequencies of each lead, vowel and tail in modern prose,
+// in the order of the crate's tables. Tail 0 is no tail.
+const LEAD_WEIGHTS: [u32; 19] = [
+    120, 10, 60, 80, 8, 40, 45, 45, 3, 80,
+    10, 220, 80, 3, 25, 8, 15, 12, 70, ];
+const VOWEL_WEIGHTS: [u32; 21] = [
+    220, 40, 8, 1, 120, 50, 40, 5, 100, 20,
+    2, 12, 15, 70, 8, 1, 8, 8, 90, 20,
+    160, ];
+const TAIL_WEIGHTS: [u32; 28] = [
+    550, 50, 2, 1, 110, 1, 2, 5, 80, 3,
+    2, 2, 1, 1, 1, 1, 30, 25, 3, 15,
+    20, 100, 3, 3, 1, 2, 3, 3, ];
+
+/// A seeded generator of random Hangul text for benchmarks and property
+/// tests. The same seed always gives the same text.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::gen::Generator;
+/// use jamo::hangul::KoreanSentence;
+/// let mut generator = Generator::new(42).weighted(true);
+/// let sentence = generator.sentence();
+/// assert!(sentence.ends_with('.'));
+/// assert_eq!(sentence, Generator::new(42).weighted(true).sentence());
+/// assert!(KoreanSentence::new(&generator.word()).letters().iter().all(|l| l.is_hangul()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Generator {
+    state: u64,
+    weighted: bool,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed, weighted: false }
+    }
+
+    /// Draws jamo by their frequency in real text instead of uniformly.
+    pub fn weighted(mut self, weighted: bool) -> Self {
+        self.weighted = weighted;
+        self
+    }
+
+    /// Returns a random precomposed syllable.
+    pub fn syllable(&mut self) -> char {
+        let (lead, vowel, tail) = if self.weighted {
+            (self.pick(&LEAD_WEIGHTS), self.pick(&VOWEL_WEIGHTS), self.pick(&TAIL_WEIGHTS))
+        } else {
+            (self.below(19), self.below(21), self.below(28))
+        };
+        blocks::compose_indices(lead, vowel, tail).unwrap_or('가')
+    }
+
+    /// Returns a word of one to four syllables.
+    pub fn word(&mut self) -> String {
+        let len = 1 + self.below(4);
+        (0..len).map(|_| self.syllable()).collect()
+    }
+
+    /// Returns two to eight words separated by spaces and ending in a period.
+    pub fn sentence(&mut self) -> String {
+        let len = 2 + self.below(7);
+        let words = (0..len).map(|_| self.word()).collect::<Vec<String>>();
+        format!("{}.", words.join(" "))
+    }
+
+    // SplitMix64.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn pick(&mut self, weights: &[u32]) -> usize {
+        let mut r = self.below(weights.iter().sum::<u32>() as usize) as u32;
+        for (i, &w) in weights.iter().enumerate() {
+            if r < w {
+                return i;
+            }
+            r -= w;
+        }
+        0
+    }
+}
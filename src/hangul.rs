@@ -1,37 +1,223 @@
 use core::char;
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
 use std::iter::FromIterator;
+use std::ops::{Deref, Range};
+use std::sync::OnceLock;
+
+use crate::blocks::{block, Block, Position};
+use crate::phonology::Features;
+use crate::rules::{Reduction, Rule, RuleProfile, Shift, STANDARD};
 
 
 const JAMO_OFFSET: usize = 0xac00;
+const SYLLABLE_COUNT: usize = 11172;
 const LEAD_OFFSET: usize = 0x1100;
 const VOWEL_OFFSET: usize = 0x1161;
 const TAIL_OFFSET: usize = 0x11a7;
 
-const LEAD_DICT: [&str; 19] = [
+pub(crate) const LEAD_DICT: [&str; 19] = [
     "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s",
     "ss", "", "j", "tch", "ch", "k", "t", "p", "h", ];
-const VOWEL_DICT: [&str; 21] = [
+pub(crate) const VOWEL_DICT: [&str; 21] = [
     "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa",
     "wae", "oe", "yo", "u", "weo", "we", "wi", "yu", "eu", "eui",
     "i", ];
-const TAIL_DICT: [&str; 28] = [
+pub(crate) const TAIL_DICT: [&str; 28] = [
     "", "g", "gg", "gs", "n", "nj", "nh", "d", "r", "rg",
     "rm", "rb", "rs", "rt", "rb", "rh", "m", "b", "bs", "s",
     "ss", "ng", "j", "ch", "k", "t", "p", "h", ];
 
+// Plain, tense and aspirated consonants of each series, as indices into the
+// lead and tail tables.
+const LEAD_SERIES: [[Option<usize>; 3]; 5] = [
+    [Some(0), Some(1), Some(15)], [Some(3), Some(4), Some(16)], [Some(7), Some(8), Some(17)],
+    [Some(9), Some(10), None], [Some(12), Some(13), Some(14)], ];
+const TAIL_SERIES: [[Option<usize>; 3]; 5] = [
+    [Some(1), Some(2), Some(24)], [Some(7), None, Some(25)], [Some(17), None, Some(26)],
+    [Some(19), Some(20), None], [Some(22), None, Some(23)], ];
+
+const COMPAT_OFFSET: usize = 0x3131;
+const COMPAT_VOWEL_OFFSET: usize = 0x314f;
+pub(crate) const LEAD_COMPAT: [char; 19] = [
+    'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ',
+    'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ', ];
+pub(crate) const TAIL_COMPAT: [char; 28] = [
+    '\0', 'ㄱ', 'ㄲ', 'ㄳ', 'ㄴ', 'ㄵ', 'ㄶ', 'ㄷ', 'ㄹ', 'ㄺ',
+    'ㄻ', 'ㄼ', 'ㄽ', 'ㄾ', 'ㄿ', 'ㅀ', 'ㅁ', 'ㅂ', 'ㅄ', 'ㅅ',
+    'ㅆ', 'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ', ];
+// Keys pressed on a 2-set keyboard for each compatibility jamo (U+3131..=U+3163).
+const COMPAT_STROKES: [&str; 51] = [
+    "ㄱ", "ㄲ", "ㄱㅅ", "ㄴ", "ㄴㅈ", "ㄴㅎ", "ㄷ", "ㄸ", "ㄹ", "ㄹㄱ",
+    "ㄹㅁ", "ㄹㅂ", "ㄹㅅ", "ㄹㅌ", "ㄹㅍ", "ㄹㅎ", "ㅁ", "ㅂ", "ㅃ", "ㅂㅅ",
+    "ㅅ", "ㅆ", "ㅇ", "ㅈ", "ㅉ", "ㅊ", "ㅋ", "ㅌ", "ㅍ", "ㅎ",
+    "ㅏ", "ㅐ", "ㅑ", "ㅒ", "ㅓ", "ㅔ", "ㅕ", "ㅖ", "ㅗ", "ㅗㅏ",
+    "ㅗㅐ", "ㅗㅣ", "ㅛ", "ㅜ", "ㅜㅓ", "ㅜㅔ", "ㅜㅣ", "ㅠ", "ㅡ", "ㅡㅣ",
+    "ㅣ", ];
+
+fn compat_strokes(c: char) -> Option<&'static str> {
+    (c as usize).checked_sub(COMPAT_OFFSET).and_then(|i| COMPAT_STROKES.get(i)).copied()
+}
+
+/// Returns the compatibility jamo typed as `a` then `b`, e.g. ㅘ for ㅗ, ㅏ.
+pub(crate) fn compat_compound(a: char, b: char) -> Option<char> {
+    let strokes = format!("{}{}", a, b);
+    COMPAT_STROKES.iter().position(|s| *s == strokes)
+        .and_then(|i| char::from_u32((i + COMPAT_OFFSET) as u32))
+}
+
+fn offset_index(c: char, offset: usize, len: usize) -> Option<usize> {
+    (c as usize).checked_sub(offset).filter(|&i| i < len)
+}
+
+fn is_jamo(c: char) -> bool {
+    ('\u{1100}'..='\u{11ff}').contains(&c) || ('\u{3131}'..='\u{318e}').contains(&c)
+}
+
+/// Returns the lead, vowel and tail indices of the syllable `c`, or `None`
+/// if `c` is not a precomposed syllable. Tail 0 is no tail.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::decompose;
+/// const HAN: Option<(u8, u8, u8)> = decompose('한');
+/// assert_eq!(Some((18, 0, 4)), HAN);
+/// assert_eq!(None, decompose('a'));
+/// ```
+pub const fn decompose(c: char) -> Option<(u8, u8, u8)> {
+    let code = c as usize;
+    if code < JAMO_OFFSET || code >= JAMO_OFFSET + SYLLABLE_COUNT {
+        return None;
+    }
+    let rem = code - JAMO_OFFSET;
+    Some(((rem / 588) as u8, (rem % 588 / 28) as u8, (rem % 28) as u8))
+}
+
+/// Renders `s` at compile time for the [`roman!`](crate::roman!) and
+/// [`jamo!`](crate::jamo!) macros, returning the bytes and the full length,
+/// which may exceed `N`.
+#[doc(hidden)]
+pub const fn render_const<const N: usize>(s: &str, roman: bool) -> ([u8; N], usize) {
+    let bytes = s.as_bytes();
+    let mut out = [0; N];
+    let (mut i, mut len) = (0, 0);
+    while i < bytes.len() {
+        let width = match bytes[i] {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            _ => 4,
+        };
+        let code = match width {
+            1 => bytes[i] as u32,
+            2 => (bytes[i] as u32 & 0x1f) << 6 | (bytes[i + 1] as u32 & 0x3f),
+            3 => (bytes[i] as u32 & 0x0f) << 12 | (bytes[i + 1] as u32 & 0x3f) << 6
+                | (bytes[i + 2] as u32 & 0x3f),
+            _ => (bytes[i] as u32 & 0x07) << 18 | (bytes[i + 1] as u32 & 0x3f) << 12
+                | (bytes[i + 2] as u32 & 0x3f) << 6 | (bytes[i + 3] as u32 & 0x3f),
+        };
+        let syllable = match char::from_u32(code) {
+            Some(c) => decompose(c),
+            None => None,
+        };
+        match syllable {
+            Some((lead, vowel, tail)) if roman => {
+                len = push_const(&mut out, len, LEAD_DICT[lead as usize].as_bytes());
+                len = push_const(&mut out, len, VOWEL_DICT[vowel as usize].as_bytes());
+                len = push_const(&mut out, len, TAIL_DICT[tail as usize].as_bytes());
+            }
+            Some((lead, vowel, tail)) => {
+                len = push_jamo_const(&mut out, len, LEAD_OFFSET + lead as usize);
+                len = push_jamo_const(&mut out, len, VOWEL_OFFSET + vowel as usize);
+                if tail > 0 {
+                    len = push_jamo_const(&mut out, len, TAIL_OFFSET + tail as usize);
+                }
+            }
+            None => {
+                let mut k = 0;
+                while k < width {
+                    len = push_const(&mut out, len, &[bytes[i + k]]);
+                    k += 1;
+                }
+            }
+        }
+        i += width;
+    }
+    (out, len)
+}
+
+const fn push_const<const N: usize>(out: &mut [u8; N], mut len: usize, bytes: &[u8]) -> usize {
+    let mut k = 0;
+    while k < bytes.len() {
+        if len < N {
+            out[len] = bytes[k];
+        }
+        len += 1;
+        k += 1;
+    }
+    len
+}
+
+// Conjoining jamo are all three bytes in UTF-8.
+const fn push_jamo_const<const N: usize>(out: &mut [u8; N], len: usize, code: usize) -> usize {
+    push_const(out, len, &[0xe0 | (code >> 12) as u8, 0x80 | (code >> 6 & 0x3f) as u8, 0x80 | (code & 0x3f) as u8])
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn in_dict(s: &str, dict: &[&str]) -> bool {
+    let mut i = 0;
+    while i < dict.len() {
+        if str_eq(s, dict[i]) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Checks a lead pattern of the [`rules!`](crate::rules!) macro.
+#[doc(hidden)]
+pub const fn is_lead_pattern(s: &str) -> bool {
+    str_eq(s, "*") || in_dict(s, &LEAD_DICT)
+}
+
+/// Checks a tail pattern of the [`rules!`](crate::rules!) macro.
+#[doc(hidden)]
+pub const fn is_tail_pattern(s: &str) -> bool {
+    str_eq(s, "*") || in_dict(s, &TAIL_DICT)
+}
+
 fn reverse_dict(s: &[&'static str]) -> HashMap<&'static str, usize> {
     HashMap::from_iter(s.iter().enumerate().map(|(i, v)| (*v, i)))
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum JamoPosition {
     Lead,
     Vowel,
     Tail,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Jamo {
     usize: usize,
     position: JamoPosition,
@@ -39,17 +225,160 @@ pub struct Jamo {
 
 impl Jamo {
     pub fn roman(&self) -> &'static str {
-        return match self.position {
+        match self.position {
             JamoPosition::Lead => LEAD_DICT[self.usize],
             JamoPosition::Vowel => VOWEL_DICT[self.usize],
             JamoPosition::Tail => TAIL_DICT[self.usize],
-        };
+        }
+    }
+    pub fn index(&self) -> usize {
+        self.usize
     }
     fn jamo_char_from_usize(u: usize, offset: usize) -> char {
-        char::from_u32((u + offset) as u32).unwrap()
+        char::from_u32((u + offset) as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+    /// Returns the compatibility jamo (U+3131..) for this jamo, or `None` for an empty tail.
+    pub fn compat_char(&self) -> Option<char> {
+        match self.position {
+            JamoPosition::Lead => Some(LEAD_COMPAT[self.usize]),
+            JamoPosition::Vowel => char::from_u32((self.usize + COMPAT_VOWEL_OFFSET) as u32),
+            JamoPosition::Tail if self.usize == 0 => None,
+            JamoPosition::Tail => Some(TAIL_COMPAT[self.usize]),
+        }
+    }
+    /// Returns the basic jamo typed on a 2-set keyboard to produce this jamo,
+    /// e.g. "ㅗㅏ" for ㅘ and "ㄹㄱ" for ㄺ.
+    pub fn strokes(&self) -> &'static str {
+        self.compat_char().and_then(compat_strokes).unwrap_or("")
+    }
+    /// Returns the tense consonant of the same series (ㄱ, ㄲ, ㅋ → ㄲ), or
+    /// `None` for vowels and consonants without one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use jamo::hangul::Jamo;
+    /// let jamo = |c| Jamo::try_from(c).unwrap();
+    /// assert_eq!(Some(jamo('ㄲ')), jamo('ㄱ').tense());
+    /// assert_eq!(Some(jamo('ㅋ')), jamo('ㄲ').aspirated());
+    /// assert_eq!(Some(jamo('ㅈ')), jamo('ㅊ').plain());
+    /// assert_eq!(None, jamo('ㅅ').aspirated());
+    /// assert_eq!(None, jamo('ㅏ').tense());
+    /// ```
+    pub fn tense(&self) -> Option<Jamo> {
+        self.in_series(1)
+    }
+    /// Returns the plain consonant of the same series (ㄱ, ㄲ, ㅋ → ㄱ).
+    pub fn plain(&self) -> Option<Jamo> {
+        self.in_series(0)
+    }
+    /// Returns the aspirated consonant of the same series (ㄱ, ㄲ, ㅋ → ㅋ).
+    pub fn aspirated(&self) -> Option<Jamo> {
+        self.in_series(2)
+    }
+    pub fn is_vowel(&self) -> bool {
+        self.position == JamoPosition::Vowel
+    }
+    /// ㄲ, ㄸ, ㅃ, ㅆ and ㅉ.
+    ///
+    /// The consonant predicates hold for single consonants only: compound
+    /// tails such as ㄳ and the empty tail are none of these, and neither is
+    /// ㅇ as a lead, which is silent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use jamo::hangul::Jamo;
+    /// let jamo = |c| Jamo::try_from(c).unwrap();
+    /// assert!(jamo('ㄲ').is_tense() && jamo('ㅋ').is_aspirated());
+    /// assert!(jamo('ㅁ').is_nasal() && jamo('ㄹ').is_liquid());
+    /// assert!(jamo('ㅎ').is_obstruent() && !jamo('ㄴ').is_obstruent());
+    /// assert!(jamo('ㅟ').is_front() && jamo('ㅟ').is_rounded());
+    /// assert!(jamo('ㅠ').is_y_diphthong() && !jamo('ㅘ').is_y_diphthong());
+    /// ```
+    pub fn is_tense(&self) -> bool {
+        self.tense().as_ref() == Some(self)
+    }
+    /// ㅋ, ㅌ, ㅍ and ㅊ.
+    pub fn is_aspirated(&self) -> bool {
+        self.aspirated().as_ref() == Some(self)
+    }
+    /// ㄴ, ㅁ and ㅇ as a tail.
+    pub fn is_nasal(&self) -> bool {
+        match self.position {
+            JamoPosition::Lead => matches!(self.usize, 2 | 6),
+            JamoPosition::Vowel => false,
+            JamoPosition::Tail => matches!(self.usize, 4 | 16 | 21),
+        }
+    }
+    /// ㄹ.
+    pub fn is_liquid(&self) -> bool {
+        match self.position {
+            JamoPosition::Lead => self.usize == 5,
+            JamoPosition::Vowel => false,
+            JamoPosition::Tail => self.usize == 8,
+        }
+    }
+    /// The stops, fricatives and affricates: every single consonant but the
+    /// nasals and ㄹ.
+    pub fn is_obstruent(&self) -> bool {
+        let single = match self.position {
+            JamoPosition::Lead => self.usize != 11,
+            JamoPosition::Vowel => false,
+            // No tail, or a compound one.
+            JamoPosition::Tail => !matches!(self.usize, 0 | 3 | 5 | 6 | 9..=15 | 18),
+        };
+        single && !self.is_nasal() && !self.is_liquid()
+    }
+    /// Vowels whose nucleus is front: ㅣ, ㅔ, ㅐ, ㅟ, ㅚ and the diphthongs
+    /// ending in them.
+    pub fn is_front(&self) -> bool {
+        self.is_vowel() && matches!(self.usize, 1 | 3 | 5 | 7 | 10 | 11 | 15 | 16 | 19 | 20)
+    }
+    /// Vowels whose nucleus is rounded: ㅗ, ㅜ, ㅚ, ㅟ, ㅛ and ㅠ.
+    pub fn is_rounded(&self) -> bool {
+        self.is_vowel() && matches!(self.usize, 8 | 11 | 12 | 13 | 16 | 17)
+    }
+    /// ㅑ, ㅒ, ㅕ, ㅖ, ㅛ and ㅠ.
+    pub fn is_y_diphthong(&self) -> bool {
+        self.is_vowel() && matches!(self.usize, 2 | 3 | 6 | 7 | 12 | 17)
+    }
+    /// Returns the phonological features of a vowel or single consonant, or
+    /// `None` for compound tails, the empty tail and the silent lead ㅇ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use jamo::hangul::Jamo;
+    /// use jamo::phonology::{Features, Manner, Place};
+    /// let nasal_lead = |c| matches!(Jamo::try_from(c).unwrap().features(),
+    ///                               Some(Features::Consonant { manner: Manner::Nasal, .. }));
+    /// assert!(nasal_lead('ㅁ') && !nasal_lead('ㅂ'));
+    /// assert!(matches!(Jamo::try_from('ㅍ').unwrap().features(),
+    ///                  Some(Features::Consonant { place: Place::Bilabial, .. })));
+    /// assert_eq!(None, Jamo::try_from('ㅇ').unwrap().features());
+    /// ```
+    pub fn features(&self) -> Option<Features> {
+        match self.position {
+            JamoPosition::Lead if self.usize == 11 => None,
+            JamoPosition::Vowel => crate::phonology::vowel(self.usize),
+            _ => self.compat_char().and_then(crate::phonology::consonant),
+        }
+    }
+    fn in_series(&self, member: usize) -> Option<Jamo> {
+        let series = match self.position {
+            JamoPosition::Lead => &LEAD_SERIES[..],
+            JamoPosition::Vowel => &[],
+            JamoPosition::Tail => &TAIL_SERIES[..],
+        };
+        let series = series.iter().find(|s| s.contains(&Some(self.usize)))?;
+        series[member].map(|usize| Jamo { usize, position: self.position.clone() })
     }
     pub fn jamo_string(&self) -> String {
-        return match self.position {
+        match self.position {
             JamoPosition::Lead => Self::jamo_char_from_usize(self.usize, LEAD_OFFSET).to_string(),
             JamoPosition::Vowel => Self::jamo_char_from_usize(self.usize, VOWEL_OFFSET).to_string(),
             JamoPosition::Tail => {
@@ -58,11 +387,68 @@ impl Jamo {
                 }
                 Self::jamo_char_from_usize(self.usize, TAIL_OFFSET).to_string()
             }
-        };
+        }
     }
 }
 
-#[derive(Clone)]
+/// A char that is not a jamo, from `Jamo::try_from`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotAJamo(pub u32);
+
+impl fmt::Display for NotAJamo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "U+{:04X} is not a modern jamo", self.0)
+    }
+}
+
+impl Error for NotAJamo {}
+
+/// Reads a modern jamo from any block. A consonant that can be both is taken
+/// as a lead unless it is a conjoining tail.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use jamo::hangul::Jamo;
+/// assert_eq!("g", Jamo::try_from('ㄱ').unwrap().roman());
+/// assert_eq!("gs", Jamo::try_from('ㄳ').unwrap().roman());
+/// assert_eq!("wa", Jamo::try_from(0x116au32).unwrap().roman());
+/// assert!(Jamo::try_from('가').is_err());
+/// ```
+impl TryFrom<char> for Jamo {
+    type Error = NotAJamo;
+
+    fn try_from(c: char) -> Result<Self, NotAJamo> {
+        [(Position::Lead, JamoPosition::Lead), (Position::Vowel, JamoPosition::Vowel), (Position::Tail, JamoPosition::Tail)]
+            .iter()
+            .find_map(|(p, position)| crate::blocks::index(c, *p).map(|usize| Jamo { usize, position: position.clone() }))
+            .ok_or(NotAJamo(c as u32))
+    }
+}
+
+impl TryFrom<u32> for Jamo {
+    type Error = NotAJamo;
+
+    fn try_from(code: u32) -> Result<Self, NotAJamo> {
+        char::from_u32(code).ok_or(NotAJamo(code)).and_then(Self::try_from)
+    }
+}
+
+/// A char that is not a precomposed syllable, from `Hangul::try_from`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotASyllable(pub u32);
+
+impl fmt::Display for NotASyllable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "U+{:04X} is not a Hangul syllable", self.0)
+    }
+}
+
+impl Error for NotASyllable {}
+
+/// Syllables order by lead, then vowel, then tail, which is dictionary order.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Hangul {
     lead: Jamo,
     vowel: Jamo,
@@ -70,9 +456,41 @@ pub struct Hangul {
 }
 
 
+/// Decomposes a precomposed syllable, U+AC00..=U+D7A3.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use jamo::hangul::{Hangul, NotASyllable};
+/// assert_eq!("han", Hangul::try_from('한').unwrap().roman_string());
+/// assert_eq!(Err(NotASyllable(0x61)), Hangul::try_from('a'));
+/// assert_eq!(Err(NotASyllable(0xd800)), Hangul::try_from(0xd800u32));
+/// ```
+impl TryFrom<char> for Hangul {
+    type Error = NotASyllable;
+
+    fn try_from(c: char) -> Result<Self, NotASyllable> {
+        let (lead, vowel, tail) = decompose(c).ok_or(NotASyllable(c as u32))?;
+        Ok(Self::from_indices(lead as usize, vowel as usize, tail as usize))
+    }
+}
+
+impl TryFrom<u32> for Hangul {
+    type Error = NotASyllable;
+
+    fn try_from(code: u32) -> Result<Self, NotASyllable> {
+        char::from_u32(code).ok_or(NotASyllable(code)).and_then(Self::try_from)
+    }
+}
+
 impl Hangul {
+    /// Decomposes the syllable `c`. Chars outside U+AC00..=U+D7A3 give an
+    /// unspecified syllable instead of panicking; [`Letter::new`] checks the
+    /// range first.
+    #[deprecated(note = "gives a wrong syllable for chars that are not one; use `Hangul::try_from`")]
     pub fn new(c: char) -> Self {
-        let rem = c as usize - JAMO_OFFSET;
+        let rem = (c as usize).wrapping_sub(JAMO_OFFSET) % SYLLABLE_COUNT;
         let lead = rem / 588;
         let vowel = rem % 588 / 28;
         let tail = rem % 28;
@@ -82,9 +500,70 @@ impl Hangul {
             tail: Jamo { usize: tail, position: JamoPosition::Tail },
         }
     }
+    fn from_indices(lead: usize, vowel: usize, tail: usize) -> Self {
+        Hangul {
+            lead: Jamo { usize: lead, position: JamoPosition::Lead },
+            vowel: Jamo { usize: vowel, position: JamoPosition::Vowel },
+            tail: Jamo { usize: tail, position: JamoPosition::Tail },
+        }
+    }
+    /// Recomposes a syllable from the start of a run of stray jamo, either
+    /// conjoining (U+1100..) or compatibility jamo typed as on a 2-set
+    /// keyboard. Returns the syllable and how many chars it consumed.
+    fn recompose(chars: &[char]) -> Option<(Self, usize)> {
+        let at = |i: usize| chars.get(i).copied();
+        let conjoining = (
+            at(0).and_then(|c| offset_index(c, LEAD_OFFSET, LEAD_DICT.len())),
+            at(1).and_then(|c| offset_index(c, VOWEL_OFFSET, VOWEL_DICT.len())),
+        );
+        if let (Some(lead), Some(vowel)) = conjoining {
+            let tail = at(2).and_then(|c| offset_index(c, TAIL_OFFSET, TAIL_DICT.len()))
+                .filter(|&t| t > 0);
+            return Some((Self::from_indices(lead, vowel, tail.unwrap_or(0)), 2 + tail.map_or(0, |_| 1)));
+        }
+        let compat_vowel = |i: usize| at(i).and_then(|c| offset_index(c, COMPAT_VOWEL_OFFSET, VOWEL_DICT.len()));
+        let compat_tail = |c: char| TAIL_COMPAT.iter().skip(1).position(|&t| t == c).map(|t| t + 1);
+        let lead = at(0).and_then(|c| LEAD_COMPAT.iter().position(|&l| l == c))?;
+        let mut vowel = compat_vowel(1)?;
+        let mut n = 2;
+        if let Some(compound) = at(2).and_then(|c| compat_compound(at(1)?, c)) {
+            vowel = compound as usize - COMPAT_VOWEL_OFFSET;
+            n = 3;
+        }
+        // A consonant belongs to the next syllable when a vowel follows it.
+        let mut tail = 0;
+        if let Some(t) = at(n).and_then(compat_tail).filter(|_| compat_vowel(n + 1).is_none()) {
+            tail = t;
+            n += 1;
+            let compound = at(n).and_then(|c| compat_compound(TAIL_COMPAT[tail], c)).and_then(compat_tail);
+            if let Some(t) = compound.filter(|_| compat_vowel(n + 1).is_none()) {
+                tail = t;
+                n += 1;
+            }
+        }
+        Some((Self::from_indices(lead, vowel, tail), n))
+    }
+    /// Recombines the jamo into the precomposed syllable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::Hangul;
+    /// use std::convert::TryFrom;
+    /// let han = Hangul::try_from('한').unwrap();
+    /// assert_eq!('한', han.to_char());
+    /// assert_eq!("한", han.to_string());
+    /// ```
+    pub fn to_char(&self) -> char {
+        let code = JAMO_OFFSET + self.lead.usize * 588 + self.vowel.usize * 28 + self.tail.usize;
+        char::from_u32(code as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
     pub fn lead(&self) -> &Jamo {
         &self.lead
     }
+    pub fn vowel(&self) -> &Jamo {
+        &self.vowel
+    }
     pub fn tail(&self) -> &Jamo {
         &self.tail
     }
@@ -104,20 +583,108 @@ impl Hangul {
                 self.vowel.jamo_string(),
                 self.tail.jamo_string())
     }
+    pub fn strokes(&self) -> String {
+        format!("{}{}{}", self.lead.strokes(), self.vowel.strokes(), self.tail.strokes())
+    }
+    /// Returns the conjoining jamo code points of the syllable with the names
+    /// of their Unicode blocks, leaving out an empty tail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::Hangul;
+    /// use std::convert::TryFrom;
+    /// let codepoints = Hangul::try_from('한').unwrap().codepoints();
+    /// assert_eq!(vec![('\u{1112}', "Hangul Jamo"), ('\u{1161}', "Hangul Jamo"), ('\u{11ab}', "Hangul Jamo")], codepoints);
+    /// assert_eq!(2, Hangul::try_from('가').unwrap().codepoints().len());
+    /// ```
+    pub fn codepoints(&self) -> Vec<(char, &'static str)> {
+        let codes = [
+            LEAD_OFFSET + self.lead.usize, VOWEL_OFFSET + self.vowel.usize, TAIL_OFFSET + self.tail.usize, ];
+        codes.iter()
+            .filter(|&&code| code != TAIL_OFFSET)
+            .filter_map(|&code| char::from_u32(code as u32))
+            .map(|c| (c, block(c).map_or("", |b| b.name())))
+            .collect()
+    }
 }
 
-#[derive(Clone)]
+impl fmt::Display for Hangul {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use jamo::hangul::Hangul;
+/// assert_eq!('닭', char::from(Hangul::try_from('닭').unwrap()));
+/// ```
+impl From<Hangul> for char {
+    fn from(h: Hangul) -> char {
+        h.to_char()
+    }
+}
+
+impl From<&Hangul> for char {
+    fn from(h: &Hangul) -> char {
+        h.to_char()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Letter {
     HangulLetter(Hangul),
     OtherLetter(char),
 }
 
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// let letters = KoreanSentence::new("좋아").applied().letters().to_vec();
+/// assert_eq!(vec!['조', '아'], letters.iter().map(char::from).collect::<Vec<_>>());
+/// assert_eq!("조", String::from(letters[0].clone()));
+/// ```
+impl From<Letter> for char {
+    fn from(letter: Letter) -> char {
+        letter.to_char()
+    }
+}
+
+impl From<&Letter> for char {
+    fn from(letter: &Letter) -> char {
+        letter.to_char()
+    }
+}
+
+impl From<Letter> for String {
+    fn from(letter: Letter) -> String {
+        letter.to_char().to_string()
+    }
+}
+
+/// Letters order by code point, which for Hangul syllables is dictionary order.
+impl Ord for Letter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.to_char(), self.is_hangul()).cmp(&(other.to_char(), other.is_hangul()))
+    }
+}
+
+impl PartialOrd for Letter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Letter {
     pub fn new(c: char) -> Letter {
-        if JAMO_OFFSET <= (c as usize) && (c as usize) < 0xd74a {
-            Letter::HangulLetter(Hangul::new(c))
-        } else {
-            Letter::OtherLetter(c)
+        match decompose(c) {
+            Some((lead, vowel, tail)) =>
+                Letter::HangulLetter(Hangul::from_indices(lead as usize, vowel as usize, tail as usize)),
+            None => Letter::OtherLetter(c),
         }
     }
     pub fn roman(&self) -> String {
@@ -138,6 +705,20 @@ impl Letter {
             Self::OtherLetter(c) => c.to_string(),
         }
     }
+    /// Returns the letter as basic jamo keystrokes. Compatibility jamo are
+    /// split the same way, so a half-typed "가ㅂ" shares a prefix with "갑니다".
+    pub fn strokes(&self) -> String {
+        match self {
+            Self::HangulLetter(l) => l.strokes(),
+            Self::OtherLetter(c) => compat_strokes(*c).map_or(c.to_string(), String::from),
+        }
+    }
+    fn to_char(&self) -> char {
+        match self {
+            Self::HangulLetter(l) => l.to_char(),
+            Self::OtherLetter(c) => *c,
+        }
+    }
     pub fn is_hangul(&self) -> bool {
         if let Self::HangulLetter(_) = self {
             return true;
@@ -146,79 +727,779 @@ impl Letter {
     }
 }
 
-struct Rule {
-    tail: &'static str,
-    lead: &'static str,
-    strategy: fn(/* old_tail */&'static str, /* old_lead */&'static str)
-                 -> (/* new_tail */&'static str, /* new_lead */&'static str),
-}
-
-const RULES: [Rule; 5] = [ // under developing yet
-    Rule {
-        tail: "h",
-        lead: "",
-        strategy: |_, _| { ("", "") },
-    },
-    Rule { // 연음화
-        tail: "*",
-        lead: "",
-        strategy: |t, _| { ("", t) },
-    },
-    Rule {
-        tail: "b",
-        lead: "n",
-        strategy: |_, l| { ("m", l) },
-    },
-    Rule {
-        tail: "n",
-        lead: "h",
-        strategy: |t, _| { ("", t) },
-    },
-    Rule {
-        tail: "bs",
-        lead: "*",
-        strategy: |_, l| { if l == "" { ("p", "s") } else { ("p", l) } },
-    },
-];
+/// Where a [`Boundary`] falls within a word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryKind {
+    /// Before a lexical morpheme, as in 맨+입 or across words; ㄴ is inserted
+    /// before 이, 야, 여, 요 and 유. Particles and endings are not boundaries.
+    Morpheme,
+    /// Between the parts of a compound, where 사이시옷 also applies.
+    Compound,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Boundary {
+    /// The index of the first letter after the boundary.
+    pub index: usize,
+    pub kind: BoundaryKind,
+}
+
+/// Reports morpheme and compound boundaries to the rule engine, typically
+/// backed by an external morphological analyzer.
+pub trait BoundaryProvider {
+    /// Returns the boundaries in `text`, which has one char per letter.
+    fn boundaries(&self, text: &str) -> Vec<Boundary>;
+}
+
+/// A fixed list of boundaries, whatever the text.
+impl BoundaryProvider for [Boundary] {
+    fn boundaries(&self, _: &str) -> Vec<Boundary> {
+        self.to_vec()
+    }
+}
+
+fn nasalized(tail: &'static str) -> &'static str {
+    match tail {
+        "g" | "gg" | "gs" | "k" | "rg" => "ng",
+        "d" | "t" | "s" | "ss" | "j" | "ch" | "h" => "n",
+        "b" | "p" | "bs" => "m",
+        _ => tail,
+    }
+}
+
+/// Returns the tense form of a plain lead, given and returned romanized.
+fn tensed(lead: &'static str) -> Option<&'static str> {
+    let usize = LEAD_DICT.iter().position(|&l| l == lead)?;
+    let jamo = Jamo { usize, position: JamoPosition::Lead };
+    jamo.plain().filter(|plain| *plain == jamo)?.tense().map(|tense| tense.roman())
+}
 
-#[derive(Clone)]
 struct JamoContext {
     lead_rev_dict: HashMap<&'static str, usize>,
-    vowel_rev_dict: HashMap<&'static str, usize>,
     tail_rev_dict: HashMap<&'static str, usize>,
 }
 
+impl JamoContext {
+    /// Returns the lookup tables, built once and shared by every sentence.
+    fn shared() -> &'static Self {
+        static CONTEXT: OnceLock<JamoContext> = OnceLock::new();
+        CONTEXT.get_or_init(|| JamoContext {
+            lead_rev_dict: reverse_dict(&LEAD_DICT[..]),
+            tail_rev_dict: reverse_dict(&TAIL_DICT[..]),
+        })
+    }
+}
+
+/// One letter of a sentence as structured data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyllableRecord {
+    /// Position of the letter, counted in chars.
+    pub char_index: usize,
+    /// Position of the letter in the UTF-8 text, counted in bytes.
+    pub byte_range: Range<usize>,
+    pub original: char,
+    /// Compatibility jamo of the syllable; all `None` for non-Hangul letters.
+    pub lead: Option<char>,
+    pub vowel: Option<char>,
+    pub tail: Option<char>,
+    pub roman: String,
+}
+
+/// Byte-range correspondence between a sentence's source text and its romanization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    segments: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl Alignment {
+    /// Returns the (source, output) byte range of every letter.
+    pub fn segments(&self) -> &[(Range<usize>, Range<usize>)] {
+        &self.segments
+    }
+    /// Returns the output bytes produced from the letters overlapping `source`.
+    pub fn to_output(&self, source: Range<usize>) -> Option<Range<usize>> {
+        Self::map(self.segments.iter().map(|(s, o)| (s, o)), source)
+    }
+    /// Returns the source bytes of the letters that produced the output overlapping `output`.
+    pub fn to_source(&self, output: Range<usize>) -> Option<Range<usize>> {
+        Self::map(self.segments.iter().map(|(s, o)| (o, s)), output)
+    }
+    fn map<'a, I>(pairs: I, query: Range<usize>) -> Option<Range<usize>>
+        where I: Iterator<Item = (&'a Range<usize>, &'a Range<usize>)> {
+        pairs
+            .filter(|(from, _)| if query.start == query.end {
+                from.contains(&query.start)
+            } else {
+                from.start < query.end && query.start < from.end
+            })
+            .map(|(_, to)| to.clone())
+            .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end))
+    }
+}
+
+/// Where a letter of a sentence came from in the text it was built from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Origin {
+    pub char_index: usize,
+    pub byte_range: Range<usize>,
+    /// The source character, before any rule rewrote the letter.
+    pub original: char,
+}
+
+/// A stray jamo in text that also has precomposed syllables, as reported by
+/// [`KoreanSentence::diagnostics`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub origin: Origin,
+    /// The block of the jamo: conjoining, compatibility or halfwidth.
+    pub block: Block,
+}
+
+/// Whether a [`Run`] is Hangul syllables or anything else.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunKind {
+    HangulRun,
+    OtherRun,
+}
+
+/// A maximal stretch of letters of the same kind, as returned by
+/// [`KoreanSentence::runs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run<'a> {
+    pub kind: RunKind,
+    pub letters: &'a [Letter],
+    /// Positions of the letters in the sentence.
+    pub index: Range<usize>,
+    /// Bytes of the source text the letters came from.
+    pub byte_range: Range<usize>,
+}
+
+/// A rule that fired between two neighboring letters, as reported by
+/// [`KoreanSentence::fired_rules`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiredRule {
+    /// Index of the first of the two letters.
+    pub index: usize,
+    pub rule: Cow<'static, str>,
+}
+
+/// What [`KoreanSentence::roman_with`] does with characters that are neither
+/// Hangul syllables nor ASCII, such as emoji, CJK ideographs and symbols.
+#[derive(Clone, Copy, Debug)]
+pub enum Passthrough {
+    Keep,
+    Drop,
+    Replace(char),
+    Callback(fn(char) -> String),
+}
+
+// Korean and full-width punctuation with their ASCII counterparts.
+const ASCII_PUNCTUATION: [(char, &str); 22] = [
+    ('？', "?"), ('！', "!"), ('，', ","), ('．', "."), ('。', "."), ('、', ","),
+    ('·', "."), ('・', "."), ('…', "..."), ('：', ":"), ('；', ";"), ('～', "~"),
+    ('（', "("), ('）', ")"), ('「', "\""), ('」', "\""), ('『', "\""), ('』', "\""),
+    ('“', "\""), ('”', "\""), ('‘', "'"), ('’', "'"), ];
+
+/// Output normalization applied by [`KoreanSentence::roman`],
+/// [`KoreanSentence::jamo`] and [`KoreanSentence::pronounced`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Normalization {
+    /// Replaces each run of whitespace with a single space.
+    pub collapse_spaces: bool,
+    /// Replaces Korean and full-width punctuation with ASCII.
+    pub ascii_punctuation: bool,
+    /// Removes leading and trailing whitespace.
+    pub trim: bool,
+}
+
+impl Normalization {
+    pub fn all() -> Self {
+        Self { collapse_spaces: true, ascii_punctuation: true, trim: true }
+    }
+
+    pub fn apply(&self, s: &str) -> String {
+        let mut result = String::with_capacity(s.len());
+        let mut in_space = false;
+        for c in s.chars() {
+            if self.collapse_spaces && c.is_whitespace() {
+                if !in_space {
+                    result.push(' ');
+                }
+                in_space = true;
+                continue;
+            }
+            in_space = false;
+            match ASCII_PUNCTUATION.iter().find(|(p, _)| *p == c) {
+                Some((_, ascii)) if self.ascii_punctuation => result.push_str(ascii),
+                _ => result.push(c),
+            }
+        }
+        if self.trim {
+            result.trim().to_string()
+        } else {
+            result
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct KoreanSentence {
     payload: Vec<Letter>,
-    context: JamoContext,
+    origins: Vec<Origin>,
+    // The letters before rule application, kept by `applied()` so that edits
+    // can re-run the rules around the edited letters.
+    unapplied: Option<Vec<Letter>>,
+    normalization: Normalization,
+    rules: Cow<'static, [Rule]>,
+    context: &'static JamoContext,
+}
+
+/// Sentences compare by their letters only, in Korean dictionary order.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// let mut words = vec![KoreanSentence::new("다리"), KoreanSentence::new("가방"), KoreanSentence::new("나무")];
+/// words.sort();
+/// assert_eq!(vec!["gabang", "namu", "dari"], words.iter().map(|w| w.roman()).collect::<Vec<_>>());
+/// ```
+impl PartialEq for KoreanSentence {
+    fn eq(&self, other: &Self) -> bool {
+        self.payload == other.payload
+    }
+}
+
+impl Eq for KoreanSentence {}
+
+impl Ord for KoreanSentence {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.payload.cmp(&other.payload)
+    }
+}
+
+impl PartialOrd for KoreanSentence {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A sentence derefs to its letters, so slice methods work on it directly.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// let sentence = KoreanSentence::new("안녕 친구");
+/// assert_eq!(5, sentence.len());
+/// assert_eq!(2, sentence.split(|l| !l.is_hangul()).count());
+/// assert_eq!(Some(2), sentence.iter().position(|l| !l.is_hangul()));
+/// assert!(sentence.windows(2).any(|pair| pair[0].is_hangul() && pair[1].is_hangul()));
+/// ```
+impl Deref for KoreanSentence {
+    type Target = [Letter];
+
+    fn deref(&self) -> &[Letter] {
+        &self.payload
+    }
+}
+
+impl AsRef<[Letter]> for KoreanSentence {
+    fn as_ref(&self) -> &[Letter] {
+        &self.payload
+    }
 }
 
 impl KoreanSentence {
     pub fn new(s: &str) -> Self {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("parse", bytes = s.len()).entered();
         Self {
-            payload: s.chars().map(
-                |c| Letter::new(c)
-            ).collect::<Vec<Letter>>(),
-            context: JamoContext {
-                lead_rev_dict: reverse_dict(&LEAD_DICT[..]),
-                vowel_rev_dict: reverse_dict(&VOWEL_DICT[..]),
-                tail_rev_dict: reverse_dict(&TAIL_DICT[..]),
-            },
+            payload: s.chars().map(Letter::new).collect::<Vec<Letter>>(),
+            origins: s.char_indices().enumerate().map(|(i, (offset, c))| Origin {
+                char_index: i,
+                byte_range: offset..offset + c.len_utf8(),
+                original: c,
+            }).collect(),
+            unapplied: None,
+            normalization: Normalization::default(),
+            rules: Cow::Borrowed(&STANDARD),
+            context: JamoContext::shared(),
+        }
+    }
+
+    /// Builds a sentence from EUC-KR or CP949 bytes, as found in legacy
+    /// Korean corpora. Malformed sequences become U+FFFD.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// assert_eq!("hangeur", KoreanSentence::from_euc_kr(&[0xc7, 0xd1, 0xb1, 0xdb]).roman());
+    /// // 똠 is only in CP949.
+    /// assert_eq!("ttom", KoreanSentence::from_euc_kr(&[0x8c, 0x63]).roman());
+    /// ```
+    #[cfg(feature = "encoding")]
+    pub fn from_euc_kr(bytes: &[u8]) -> Self {
+        let (text, _) = encoding_rs::EUC_KR.decode_without_bom_handling(bytes);
+        Self::new(&text)
+    }
+
+    /// Builds a sentence from `s` after NFKC normalization, so fullwidth
+    /// Latin, halfwidth jamo and circled or parenthesized Hangul become
+    /// ordinary letters. Jamo left standing alone are written as
+    /// compatibility jamo. Origins point into the normalized text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// assert_eq!("KBS ga ㅋㅋ", KoreanSentence::from_nfkc("ＫＢＳ ㉮ \u{ffbb}\u{ffbb}").roman());
+    /// assert_eq!("han", KoreanSentence::from_nfkc("\u{1112}\u{1161}\u{11ab}").roman());
+    /// ```
+    #[cfg(feature = "nfkc")]
+    pub fn from_nfkc(s: &str) -> Self {
+        use unicode_normalization::UnicodeNormalization;
+        Self::new(&crate::blocks::normalize(&s.nfkc().collect::<String>()))
+    }
+
+    /// Builds a sentence from text with broken jamo sequences, recomposing
+    /// conjoining or compatibility jamo into syllables where possible.
+    ///
+    /// Returns the sentence and the origins of the jamo that could not be
+    /// recomposed, which are kept as `OtherLetter`. A recomposed letter's
+    /// origin spans all of its source chars.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let (sentence, strays) = KoreanSentence::repaired("ㄱㅏㄴ다 ㅈ");
+    /// assert_eq!("ganda ㅈ", sentence.roman());
+    /// assert_eq!(0..9, sentence.origins()[0].byte_range);
+    /// assert_eq!(vec!['ㅈ'], strays.iter().map(|o| o.original).collect::<Vec<_>>());
+    /// ```
+    pub fn repaired(s: &str) -> (Self, Vec<Origin>) {
+        let source = Self::new(s);
+        let chars = s.chars().collect::<Vec<char>>();
+        let (mut payload, mut origins, mut strays) = (vec![], vec![], vec![]);
+        let mut i = 0;
+        while i < chars.len() {
+            match Hangul::recompose(&chars[i..]) {
+                Some((h, n)) => {
+                    let range = &source.origins[i..i + n];
+                    origins.push(Origin {
+                        char_index: range[0].char_index,
+                        byte_range: range[0].byte_range.start..range[n - 1].byte_range.end,
+                        original: h.to_char(),
+                    });
+                    payload.push(Letter::HangulLetter(h));
+                    i += n;
+                }
+                None => {
+                    if is_jamo(chars[i]) {
+                        strays.push(source.origins[i].clone());
+                    }
+                    payload.push(source.payload[i].clone());
+                    origins.push(source.origins[i].clone());
+                    i += 1;
+                }
+            }
+        }
+        let Self { normalization, rules, context, .. } = source;
+        (Self { payload, origins, unapplied: None, normalization, rules, context }, strays)
+    }
+
+    /// Builds a sentence from text with the invisible characters that
+    /// [`sanitize`](crate::sanitize::sanitize) strips removed, so they do not
+    /// split syllables apart from the rules.
+    ///
+    /// Returns the sentence and every invisible character found, stripped or
+    /// not. Origins still point into `s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let (sentence, found) = KoreanSentence::sanitized("좋\u{200b}아요");
+    /// assert_eq!("joayo", sentence.applied().roman());
+    /// assert_eq!(3..6, found[0].origin.byte_range);
+    /// ```
+    pub fn sanitized(s: &str) -> (Self, Vec<crate::sanitize::Invisible>) {
+        let found = crate::sanitize::find_invisible(s);
+        let stripped = found.iter().filter(|i| crate::sanitize::is_stripped(i)).map(|i| i.origin.char_index).collect::<Vec<usize>>();
+        let mut sentence = Self::new(s);
+        let mut index = 0;
+        sentence.retain(|_| {
+            index += 1;
+            stripped.binary_search(&(index - 1)).is_err()
+        });
+        (sentence, found)
+    }
+
+    /// Returns where each letter came from. Rule application keeps origins,
+    /// so they still point into the text the sentence was first built from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let sentence = KoreanSentence::new("좋아요").applied();
+    /// assert_eq!('좋', sentence.origins()[0].original);
+    /// assert_eq!(3..6, sentence.origins()[1].byte_range);
+    /// ```
+    pub fn origins(&self) -> &[Origin] {
+        &self.origins
+    }
+
+    /// Reports the jamo in the source text when it also has precomposed
+    /// syllables, a sign of encoding damage such as half-decomposed text.
+    /// Text of jamo alone, like "ㅋㅋ", reports nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::blocks::Block;
+    /// use jamo::hangul::KoreanSentence;
+    /// let diagnostics = KoreanSentence::new("한\u{1100}\u{1173}\u{11af} ㅋ").diagnostics();
+    /// assert_eq!(vec![(1, Block::Conjoining), (2, Block::Conjoining), (3, Block::Conjoining), (5, Block::Compatibility)],
+    ///            diagnostics.iter().map(|d| (d.origin.char_index, d.block)).collect::<Vec<_>>());
+    /// assert!(KoreanSentence::new("ㅋㅋ").diagnostics().is_empty());
+    /// ```
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        if !self.origins.iter().any(|o| decompose(o.original).is_some()) {
+            return vec![];
+        }
+        self.origins.iter()
+            .filter_map(|o| match block(o.original) {
+                Some(b @ (Block::Conjoining | Block::Compatibility | Block::Halfwidth)) => {
+                    Some(Diagnostic { origin: o.clone(), block: b })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn letters(&self) -> &[Letter] {
+        &self.payload
+    }
+
+    /// Returns the indices of the letters that are neither Hangul nor whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// assert_eq!(vec![3, 4], KoreanSentence::new("안녕 hi").non_hangul_positions());
+    /// ```
+    pub fn non_hangul_positions(&self) -> Vec<usize> {
+        self.payload.iter().enumerate()
+            .filter(|(_, l)| !l.is_hangul() && !l.to_char().is_whitespace())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Splits the sentence into alternating runs of Hangul and of other
+    /// letters, so Korean spans can be handled apart from the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::{KoreanSentence, RunKind};
+    /// let sentence = KoreanSentence::new("Hello 안녕!");
+    /// let runs = sentence.runs();
+    /// assert_eq!(vec![RunKind::OtherRun, RunKind::HangulRun, RunKind::OtherRun],
+    ///            runs.iter().map(|r| r.kind).collect::<Vec<_>>());
+    /// assert_eq!(6..12, runs[1].byte_range);
+    /// let marked = runs.iter().map(|r| match r.kind {
+    ///     RunKind::HangulRun => format!("[{}]", r.letters.iter().map(|l| l.roman()).collect::<String>()),
+    ///     RunKind::OtherRun => r.letters.iter().map(char::from).collect(),
+    /// }).collect::<String>();
+    /// assert_eq!("Hello [annyeong]!", marked);
+    /// ```
+    pub fn runs(&self) -> Vec<Run<'_>> {
+        let mut start = 0;
+        self.payload.chunk_by(|a, b| a.is_hangul() == b.is_hangul()).map(|letters| {
+            let index = start..start + letters.len();
+            start = index.end;
+            let origins = &self.origins[index.clone()];
+            Run {
+                kind: if letters[0].is_hangul() { RunKind::HangulRun } else { RunKind::OtherRun },
+                letters,
+                byte_range: origins[0].byte_range.start..origins[origins.len() - 1].byte_range.end,
+                index,
+            }
+        }).collect()
+    }
+
+    /// Sets the normalization applied to the text outputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::{KoreanSentence, Normalization};
+    /// let sentence = KoreanSentence::new(" 좋아요？  네·아니요 ").with_normalization(Normalization::all());
+    /// assert_eq!("johayo? ne.aniyo", sentence.roman());
+    /// assert_eq!("조아요? 네.아니요", sentence.pronounced());
+    /// ```
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Sets the rules [`applied`](Self::applied) uses, [`STANDARD`] by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// use jamo::rules;
+    /// let sentence = KoreanSentence::new("결혼");
+    /// assert_eq!("gyeorhon", sentence.clone().with_rules(rules::profile("none").unwrap()).applied().roman());
+    /// assert_eq!("gyeoron", sentence.with_rules(&rules::COLLOQUIAL[..]).applied().roman());
+    /// ```
+    pub fn with_rules<R: Into<Cow<'static, [Rule]>>>(mut self, rules: R) -> Self {
+        self.rules = rules.into();
+        self
+    }
+
+    /// Uses the rules of `profile` and applies its reductions and shifts.
+    pub fn with_profile(mut self, profile: RuleProfile) -> Self {
+        self.reduce(profile.reductions());
+        self.shift(profile.shifts());
+        self.rewrite_word_starts(profile.tensing());
+        self.with_rules(profile.rules())
+    }
+    /// Replaces jamo throughout the sentence, before and after rules.
+    pub fn shift(&mut self, shifts: &[Shift]) {
+        let shift_letter = |letter: &mut Letter| {
+            if let Letter::HangulLetter(h) = letter {
+                for shift in shifts {
+                    let (jamo, dict, from, to) = match shift {
+                        Shift::Lead(from, to) => (&mut h.lead, &LEAD_DICT[..], from, to),
+                        Shift::Vowel(from, to) => (&mut h.vowel, &VOWEL_DICT[..], from, to),
+                    };
+                    if dict[jamo.usize] == from {
+                        if let Some(i) = dict.iter().position(|d| d == to) {
+                            jamo.usize = i;
+                        }
+                    }
+                }
+            }
+        };
+        self.payload.iter_mut().for_each(shift_letter);
+        if let Some(unapplied) = self.unapplied.as_mut() {
+            unapplied.iter_mut().for_each(shift_letter);
+        }
+    }
+    /// Rewrites the syllables at the end of each word that match a reduction,
+    /// trying reductions in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// use jamo::rules::COLLOQUIAL_REDUCTIONS;
+    /// let mut sentence = KoreanSentence::new("그것을 먹을 것이다");
+    /// sentence.reduce(&COLLOQUIAL_REDUCTIONS);
+    /// assert_eq!("geugeor meogeur geosida", sentence.roman());
+    /// ```
+    pub fn reduce(&mut self, reductions: &[Reduction]) {
+        let mut i = 0;
+        while i < self.payload.len() {
+            let found = reductions.iter().find(|r| {
+                let end = i + r.from.chars().count();
+                end <= self.payload.len()
+                    && self.payload[i..end].iter().map(Letter::to_char).eq(r.from.chars())
+                    && !self.payload.get(end).is_some_and(Letter::is_hangul)
+            });
+            match found {
+                Some(r) => {
+                    self.replace(i..i + r.from.chars().count(), &r.to);
+                    i += r.to.chars().count().max(1);
+                }
+                None => i += 1,
+            }
+        }
+    }
+
+    /// Rewrites the syllables at the start of each word that match a
+    /// rewrite, trying rewrites in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// use jamo::rules::GYEONGSANG_TENSING;
+    /// let mut sentence = KoreanSentence::new("작은 고추가 조금");
+    /// sentence.rewrite_word_starts(&GYEONGSANG_TENSING);
+    /// assert_eq!("tchageun kkochuga tchogeum", sentence.roman());
+    /// ```
+    pub fn rewrite_word_starts(&mut self, rewrites: &[Reduction]) {
+        let mut i = 0;
+        while i < self.payload.len() {
+            let word_start = i == 0 || !self.payload[i - 1].is_hangul();
+            let found = rewrites.iter().filter(|_| word_start).find(|r| {
+                let end = i + r.from.chars().count();
+                end <= self.payload.len() && self.payload[i..end].iter().map(Letter::to_char).eq(r.from.chars())
+            });
+            match found {
+                Some(r) => {
+                    self.replace(i..i + r.from.chars().count(), &r.to);
+                    i += r.to.chars().count().max(1);
+                }
+                None => i += 1,
+            }
         }
     }
 
     pub fn roman(&self) -> String {
-        self.payload.iter().map(|l| l.roman()).collect::<Vec<String>>().join("")
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("romanize", letters = self.payload.len()).entered();
+        let roman = self.payload.iter().map(|l| l.roman()).collect::<Vec<String>>().join("");
+        self.normalization.apply(&roman)
+    }
+
+    /// Romanizes the sentence, handling non-ASCII, non-Hangul characters
+    /// according to `passthrough`. ASCII is always kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::{KoreanSentence, Passthrough};
+    /// let sentence = KoreanSentence::new("한국 🇰🇷 漢字!");
+    /// assert_eq!("hangug  !", sentence.roman_with(Passthrough::Drop));
+    /// assert_eq!("hangug ?? ??!", sentence.roman_with(Passthrough::Replace('?')));
+    /// let escaped = sentence.roman_with(Passthrough::Callback(|c| format!("<U+{:04X}>", c as u32)));
+    /// assert_eq!("hangug <U+1F1F0><U+1F1F7> <U+6F22><U+5B57>!", escaped);
+    /// ```
+    pub fn roman_with(&self, passthrough: Passthrough) -> String {
+        let roman = self.payload.iter().map(|l| match (l, passthrough) {
+            (Letter::OtherLetter(c), _) if c.is_ascii() => c.to_string(),
+            (Letter::OtherLetter(_), Passthrough::Drop) => String::new(),
+            (Letter::OtherLetter(_), Passthrough::Replace(r)) => r.to_string(),
+            (Letter::OtherLetter(c), Passthrough::Callback(f)) => f(*c),
+            _ => l.roman(),
+        }).collect::<Vec<String>>().join("");
+        self.normalization.apply(&roman)
     }
 
     pub fn jamo(&self) -> String {
-        self.payload.iter().map(|l| l.jamo()).collect::<Vec<String>>().join("")
+        let jamo = self.payload.iter().map(|l| l.jamo()).collect::<Vec<String>>().join("");
+        self.normalization.apply(&jamo)
+    }
+
+    /// Returns the rule-applied sentence as precomposed text.
+    pub fn pronounced(&self) -> String {
+        let pronounced = self.applied().payload.iter().map(|l| l.to_char()).collect::<String>();
+        self.normalization.apply(&pronounced)
     }
 
     pub fn hangul_string(&self) -> String {
         self.payload.iter().map(|l| l.hangul_string()).collect::<Vec<String>>().join("")
     }
 
+    /// Returns the rule-applied pronunciation approximated in katakana.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// assert_eq!("アンニョン", KoreanSentence::new("안녕").kana());
+    /// assert_eq!("カムサハムニダ", KoreanSentence::new("감사합니다").kana());
+    /// ```
+    pub fn kana(&self) -> String {
+        crate::kana::kana(&self.applied())
+    }
+
+    /// Renders the sentence as a table with one row per letter, showing the
+    /// source jamo next to the romanization and pronunciation after rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let table = KoreanSentence::new("좋아").format_table();
+    /// assert_eq!("original  lead  vowel  tail  roman  pronounced", table.lines().next().unwrap());
+    /// assert_eq!("좋        ㅈ    ㅗ     ㅎ    jo     조", table.lines().nth(1).unwrap());
+    /// ```
+    pub fn format_table(&self) -> String {
+        crate::table::format_table(self, &crate::table::ALL_COLUMNS)
+    }
+
+    /// Like [`format_table`](Self::format_table), with only the given columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// use jamo::table::Column;
+    /// let table = KoreanSentence::new("좋아").format_table_columns(&[Column::Original, Column::Roman]);
+    /// assert_eq!("original  roman\n좋        jo\n아        a\n", table);
+    /// ```
+    pub fn format_table_columns(&self, columns: &[crate::table::Column]) -> String {
+        crate::table::format_table(self, columns)
+    }
+
+    pub fn strokes(&self) -> String {
+        self.payload.iter().map(|l| l.strokes()).collect::<Vec<String>>().join("")
+    }
+
+    /// Returns one record per letter. Positions and `original` refer to the
+    /// source text, the jamo and romanization to the letter as it is now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let records = KoreanSentence::new("a한").records();
+    /// assert_eq!(1..4, records[1].byte_range);
+    /// assert_eq!('한', records[1].original);
+    /// assert_eq!((Some('ㅎ'), Some('ㅏ'), Some('ㄴ')), (records[1].lead, records[1].vowel, records[1].tail));
+    /// assert_eq!("han", records[1].roman);
+    /// ```
+    pub fn records(&self) -> Vec<SyllableRecord> {
+        self.payload.iter().zip(self.origins.iter()).map(|(l, origin)| {
+            let (lead, vowel, tail) = match l {
+                Letter::HangulLetter(h) =>
+                    (h.lead.compat_char(), h.vowel.compat_char(), h.tail.compat_char()),
+                Letter::OtherLetter(_) => (None, None, None),
+            };
+            SyllableRecord {
+                char_index: origin.char_index,
+                byte_range: origin.byte_range.clone(),
+                original: origin.original,
+                lead,
+                vowel,
+                tail,
+                roman: l.roman(),
+            }
+        }).collect()
+    }
+
+    /// Returns the mapping between byte ranges of the source and of `roman()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let sentence = KoreanSentence::new("안녕 친구");
+    /// let alignment = sentence.roman_alignment();
+    /// let roman = sentence.roman();
+    /// let output = alignment.to_output(7..10).unwrap();
+    /// assert_eq!("chin", &roman[output.clone()]);
+    /// assert_eq!(Some(7..10), alignment.to_source(output));
+    /// ```
+    pub fn roman_alignment(&self) -> Alignment {
+        let mut offset = 0;
+        let segments = self.records().into_iter().map(|r| {
+            let output = offset..offset + r.roman.len();
+            offset = output.end;
+            (r.byte_range, output)
+        }).collect();
+        Alignment { segments }
+    }
+
     /// Returns a KoreanSentence applied the rules.
     ///
     /// # Examples
@@ -230,53 +1511,338 @@ impl KoreanSentence {
     /// assert_eq!("조아요.", new_sentence.hangul_string());
     /// ```
     pub fn applied(&self) -> Self {
+        self.applied_traced(&mut vec![])
+    }
+    /// Like [`applied`](Self::applied), pushing the letter index and rule
+    /// index of every rule that fires onto `fired`.
+    pub(crate) fn applied_traced(&self, fired: &mut Vec<(usize, usize)>) -> Self {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("apply_rules", letters = self.payload.len()).entered();
         Self {
-            payload: self.applied_vec(
-                self.payload[0].clone(),
-                self.payload[1].clone(),
-                &self.payload[2..]),
-            context: self.context.clone(),
+            payload: self.apply_letters_traced(&self.payload, fired),
+            origins: self.origins.clone(),
+            unapplied: Some(self.unapplied.clone().unwrap_or_else(|| self.payload.clone())),
+            normalization: self.normalization,
+            rules: self.rules.clone(),
+            context: self.context,
+        }
+    }
+    /// Applies the rules like [`applied`](Self::applied), first applying
+    /// ㄴ-insertion and 사이시옷 at the boundaries `provider` reports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::{Boundary, BoundaryKind, KoreanSentence};
+    /// let compound = [Boundary { index: 2, kind: BoundaryKind::Compound }];
+    /// assert_eq!("namunnip", KoreanSentence::new("나뭇잎").applied_with_boundaries(&compound[..]).roman());
+    /// let compound = [Boundary { index: 1, kind: BoundaryKind::Compound }];
+    /// assert_eq!("naekka", KoreanSentence::new("냇가").applied_with_boundaries(&compound[..]).roman());
+    /// let morpheme = [Boundary { index: 1, kind: BoundaryKind::Morpheme }];
+    /// assert_eq!("somibur", KoreanSentence::new("솜이불").applied().roman());
+    /// assert_eq!("somnibur", KoreanSentence::new("솜이불").applied_with_boundaries(&morpheme[..]).roman());
+    /// ```
+    pub fn applied_with_boundaries<P: BoundaryProvider + ?Sized>(&self, provider: &P) -> Self {
+        let mut letters = self.unapplied.clone().unwrap_or_else(|| self.payload.clone());
+        let text = letters.iter().map(|l| l.to_char()).collect::<String>();
+        for boundary in provider.boundaries(&text) {
+            let i = boundary.index;
+            if i == 0 || i >= letters.len() {
+                continue;
+            }
+            if let Some((a, b)) = self.apply_boundary(&letters[i - 1], &letters[i], boundary.kind) {
+                letters[i - 1] = a;
+                letters[i] = b;
+            }
+        }
+        Self {
+            payload: self.apply_letters(&letters),
+            origins: self.origins.clone(),
+            unapplied: Some(letters),
+            normalization: self.normalization,
+            rules: self.rules.clone(),
+            context: self.context,
+        }
+    }
+    /// Returns the rule-applied sentence along with the indices of the letters
+    /// the rules changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let (sentence, changed) = KoreanSentence::new("좋아요 안녕").applied_with_changes();
+    /// assert_eq!("joayo annyeong", sentence.roman());
+    /// assert_eq!(vec![0], changed);
+    /// ```
+    pub fn applied_with_changes(&self) -> (Self, Vec<usize>) {
+        let applied = self.applied();
+        let changed = self.payload.iter().zip(applied.payload.iter())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(i, _)| i)
+            .collect();
+        (applied, changed)
+    }
+    /// Returns the rules that fired, in order, to turn the sentence as spelled
+    /// into this one; none unless it was rule-applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let sentence = KoreanSentence::new("좋아 국어").applied();
+    /// let fired = sentence.fired_rules();
+    /// assert_eq!(vec![0, 0, 3], fired.iter().map(|f| f.index).collect::<Vec<_>>());
+    /// assert_eq!("h-deletion", fired[0].rule);
+    /// assert!(KoreanSentence::new("국물").fired_rules().is_empty());
+    /// ```
+    pub fn fired_rules(&self) -> Vec<FiredRule> {
+        let letters = match &self.unapplied {
+            Some(letters) => letters,
+            None => return vec![],
+        };
+        let mut fired = vec![];
+        self.apply_letters_traced(letters, &mut fired);
+        fired.into_iter()
+            .map(|(index, rule)| FiredRule { index, rule: self.rules[rule].name.clone() })
+            .collect()
+    }
+    /// Returns the sentence as JSON in the versioned schema described in
+    /// [`crate::json`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let json = KoreanSentence::new("좋아").applied().to_json();
+    /// assert!(json.starts_with(r#"{"schema_version":1,"text":"좋아","roman":"joa","#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        crate::json::to_json(self)
+    }
+    /// Returns the sentence in the compact binary form of [`crate::binary`].
+    #[cfg(feature = "binary")]
+    pub fn to_binary(&self) -> Vec<u8> {
+        crate::binary::encode(self)
+    }
+    /// Inserts `s` before the letter at `index`.
+    ///
+    /// On a rule-applied sentence the rules are re-run only for the letters
+    /// next to the edit, which is all a pairwise rule can reach. An `index`
+    /// past the end appends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let mut sentence = KoreanSentence::new("좋요").applied();
+    /// sentence.insert(1, "아");
+    /// assert_eq!("joayo", sentence.roman());
+    /// ```
+    pub fn insert(&mut self, index: usize, s: &str) {
+        self.replace(index..index, s);
+    }
+    /// Removes the letters in `range`, clamped to the sentence.
+    pub fn remove(&mut self, range: Range<usize>) {
+        self.replace(range, "");
+    }
+    /// Replaces the letters in `range` with the letters of `s`.
+    ///
+    /// Inserted letters get an empty origin byte range at the edit position.
+    /// The range is clamped to the sentence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let mut sentence = KoreanSentence::new("좋다 좋아").applied();
+    /// sentence.replace(0..2, "안녕");
+    /// assert_eq!("annyeong joa", sentence.roman());
+    /// ```
+    pub fn replace(&mut self, range: Range<usize>, s: &str) {
+        let end = range.end.min(self.payload.len());
+        let range = range.start.min(end)..end;
+        let start = range.start;
+        let (char_index, byte) = match self.origins.get(start) {
+            Some(o) => (o.char_index, o.byte_range.start),
+            None => self.origins.last().map_or((0, 0), |o| (o.char_index + 1, o.byte_range.end)),
+        };
+        let letters = s.chars().map(Letter::new).collect::<Vec<Letter>>();
+        let origins = s.chars().map(|c| Origin { char_index, byte_range: byte..byte, original: c });
+        let inserted = letters.len();
+        self.origins.splice(range.clone(), origins);
+        self.payload.splice(range.clone(), letters.clone());
+        if let Some(unapplied) = self.unapplied.as_mut() {
+            unapplied.splice(range, letters);
         }
+        self.reapply(start, start + inserted);
     }
-    fn applied_vec(&self, a: Letter, b: Letter, rest: &[Letter]) -> Vec<Letter> {
-        let (_a, _b) = self.apply_rules(a, b, &RULES[..]);
-        if rest.len() == 0 {
-            return vec![_a, _b];
+    /// Replaces every occurrence of `needle` in the written letters with
+    /// `replacement`, returning how many were replaced. On a rule-applied
+    /// sentence the rules are re-run only around each edit, as with
+    /// [`replace`](Self::replace).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let mut sentence = KoreanSentence::new("좋아 좋다 싫어").applied();
+    /// assert_eq!(2, sentence.replace_all("좋", "놓"));
+    /// assert_eq!(KoreanSentence::new("놓아 놓다 싫어").applied().pronounced(), sentence.pronounced());
+    /// assert_eq!(0, sentence.replace_all("", "x"));
+    /// ```
+    pub fn replace_all(&mut self, needle: &str, replacement: &str) -> usize {
+        let needle = needle.chars().collect::<Vec<char>>();
+        if needle.is_empty() {
+            return 0;
+        }
+        let written = self.unapplied.as_ref().unwrap_or(&self.payload).iter().map(char::from).collect::<Vec<char>>();
+        let mut starts = vec![];
+        let mut i = 0;
+        while i + needle.len() <= written.len() {
+            if written[i..i + needle.len()] == needle[..] {
+                starts.push(i);
+                i += needle.len();
+            } else {
+                i += 1;
+            }
+        }
+        for &start in starts.iter().rev() {
+            self.replace(start..start + needle.len(), replacement);
+        }
+        starts.len()
+    }
+    /// Keeps only the letters for which `keep` returns true. On a
+    /// rule-applied sentence the rules are re-run, since letters that were
+    /// apart may now be neighbors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// let mut sentence = KoreanSentence::new("국, 물!");
+    /// sentence.retain(|l| !matches!(char::from(l), ',' | '!'));
+    /// assert_eq!("gug mur", sentence.roman());
+    /// assert_eq!(5..8, sentence.origins()[2].byte_range);
+    /// ```
+    pub fn retain<F: FnMut(&Letter) -> bool>(&mut self, mut keep: F) {
+        fn retain_marked<T>(items: &mut Vec<T>, kept: &[bool]) {
+            let mut kept = kept.iter();
+            items.retain(|_| kept.next() == Some(&true));
+        }
+        let kept = self.payload.iter().map(&mut keep).collect::<Vec<bool>>();
+        retain_marked(&mut self.origins, &kept);
+        retain_marked(&mut self.payload, &kept);
+        if let Some(mut unapplied) = self.unapplied.take() {
+            retain_marked(&mut unapplied, &kept);
+            self.payload = self.apply_letters(&unapplied);
+            self.unapplied = Some(unapplied);
         }
-        [vec![_a], self.applied_vec(_b, rest[0].clone(), &rest[1..])].concat()
     }
-    fn apply_rules(&self, a: Letter, b: Letter, rules: &[Rule]) -> (Letter, Letter) {
-        if rules.len() == 0 {
+    /// Returns the sentence with only its Hangul syllables, e.g. for jamo
+    /// statistics that should not count punctuation and spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// assert_eq!("joa", KoreanSentence::new("좋 - 아").hangul_only().applied().roman());
+    /// ```
+    pub fn hangul_only(mut self) -> Self {
+        self.retain(Letter::is_hangul);
+        self
+    }
+    fn reapply(&mut self, start: usize, end: usize) {
+        let unapplied = match &self.unapplied {
+            Some(unapplied) => unapplied,
+            None => return,
+        };
+        let lo = start.saturating_sub(2);
+        let hi = (end + 2).min(unapplied.len());
+        let window = self.apply_letters(&unapplied[lo..hi]);
+        let (from, to) = (start.saturating_sub(1), (end + 1).min(unapplied.len()));
+        self.payload[from..to].clone_from_slice(&window[from - lo..to - lo]);
+    }
+    fn apply_boundary(&self, a: &Letter, b: &Letter, kind: BoundaryKind) -> Option<(Letter, Letter)> {
+        let (a, b) = match (a, b) {
+            (Letter::HangulLetter(a), Letter::HangulLetter(b)) => (a, b),
+            _ => return None,
+        };
+        let (tail, lead, vowel) = (a.tail().roman(), b.lead().roman(), b.vowel().roman());
+        let palatal = vowel == "i" || vowel.starts_with('y');
+        let (new_tail, new_lead) = match (tail, lead) {
+            ("", _) => return None,
+            ("r", "") if palatal => ("r", "r"), // 물약 → 물략
+            (_, "") if palatal => (nasalized(tail), "n"), // 맨입 → 맨닙, 색연필 → 생년필
+            ("s", "n") | ("s", "m") if kind == BoundaryKind::Compound => ("n", lead), // 콧날 → 콘날
+            ("s", _) if kind == BoundaryKind::Compound => ("", tensed(lead)?), // 냇가 → 내까
+            _ => return None,
+        };
+        let new_tail = *self.context.tail_rev_dict.get(new_tail)?;
+        let new_lead = *self.context.lead_rev_dict.get(new_lead)?;
+        Some((
+            Letter::HangulLetter(Hangul::from_indices(a.lead.usize, a.vowel.usize, new_tail)),
+            Letter::HangulLetter(Hangul::from_indices(new_lead, b.vowel.usize, b.tail.usize)),
+        ))
+    }
+    fn apply_letters(&self, letters: &[Letter]) -> Vec<Letter> {
+        self.apply_letters_traced(letters, &mut vec![])
+    }
+    /// Applies the rules to each pair of letters in turn, pushing the letter
+    /// index and rule index of every rule that fires onto `fired`.
+    fn apply_letters_traced(&self, letters: &[Letter], fired: &mut Vec<(usize, usize)>) -> Vec<Letter> {
+        let mut letters = letters.iter().cloned();
+        let mut a = match letters.next() {
+            Some(a) => a,
+            None => return vec![],
+        };
+        let mut applied = vec![];
+        for b in letters {
+            let mut rules = vec![];
+            let (_a, _b) = self.apply_rules(a, b, &self.rules, &mut rules);
+            fired.extend(rules.into_iter().map(|rule| (applied.len(), rule)));
+            applied.push(_a);
+            a = _b;
+        }
+        applied.push(a);
+        applied
+    }
+    fn apply_rules(&self, a: Letter, b: Letter, rules: &[Rule], fired: &mut Vec<usize>) -> (Letter, Letter) {
+        if rules.is_empty() {
             return (a, b);
         }
         if let (Letter::HangulLetter(_a), Letter::HangulLetter(_b)) = (&a, &b) {
             let tail = _a.tail().roman();
             let lead = _b.lead().roman();
-            if (rules[0].tail == "*" || tail == rules[0].tail) &&
-                (rules[0].lead == "*" || lead == rules[0].lead) {
-                let (new_tail, new_lead) = (rules[0].strategy)(tail, lead);
-                return self.apply_rules(
-                    Letter::HangulLetter(
-                        Hangul {
-                            lead: _a.lead.clone(),
-                            vowel: _a.vowel.clone(),
-                            tail: Jamo {
-                                usize: self.context.tail_rev_dict[new_tail],
-                                position: JamoPosition::Tail,
-                            },
-                        }),
-                    Letter::HangulLetter(
-                        Hangul {
-                            lead: Jamo {
-                                usize: self.context.lead_rev_dict[new_lead],
-                                position: JamoPosition::Lead,
-                            },
-                            vowel: _b.vowel.clone(),
-                            tail: _b.tail.clone(),
-                        }),
-                    &rules[1..]);
+            if rules[0].matches(tail, lead) {
+                let (new_tail, new_lead) = rules[0].rewrite(tail, lead);
+                let new_tail = self.context.tail_rev_dict.get(new_tail);
+                let new_lead = self.context.lead_rev_dict.get(new_lead);
+                // A rule whose result has no jamo in that position (e.g. moving ㅇ or ㄳ
+                // into the lead) does not fire.
+                if let (Some(&new_tail), Some(&new_lead)) = (new_tail, new_lead) {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(rule = %rules[0].name, syllables = %format!("{}{}", _a, _b),
+                                    tail, lead, "rule fired");
+                    fired.push(self.rules.len() - rules.len());
+                    return self.apply_rules(
+                        Letter::HangulLetter(
+                            Hangul {
+                                lead: _a.lead.clone(),
+                                vowel: _a.vowel.clone(),
+                                tail: Jamo { usize: new_tail, position: JamoPosition::Tail },
+                            }),
+                        Letter::HangulLetter(
+                            Hangul {
+                                lead: Jamo { usize: new_lead, position: JamoPosition::Lead },
+                                vowel: _b.vowel.clone(),
+                                tail: _b.tail.clone(),
+                            }),
+                        &rules[1..], fired);
+                }
             }
         }
-        self.apply_rules(a, b, &rules[1..])
+        self.apply_rules(a, b, &rules[1..], fired)
     }
 }
@@ -0,0 +1,150 @@
+use crate::blocks::{self, Position};
+use crate::hangul::decompose;
+
+// Five-bit codes of each vowel and tail index; leads are the index plus 2,
+// and 1 (2 for vowels) is the fill code for an empty position.
+const VOWEL_CODES: [u16; 21] = [
+    3, 4, 5, 6, 7, 10, 11, 12, 13, 14,
+    15, 18, 19, 20, 21, 22, 23, 26, 27, 28,
+    29, ];
+const TAIL_CODES: [u16; 28] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
+    11, 12, 13, 14, 15, 16, 17, 19, 20, 21,
+    22, 23, 24, 25, 26, 27, 28, 29, ];
+const LEAD_FILL: u16 = 1;
+const VOWEL_FILL: u16 = 2;
+const TAIL_FILL: u16 = 1;
+
+fn code(lead: u16, vowel: u16, tail: u16) -> u16 {
+    0x8000 | lead << 10 | vowel << 5 | tail
+}
+
+/// Returns the two-byte Johab code of a syllable or compatibility jamo.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::johab::encode_char;
+/// assert_eq!(Some(0x8861), encode_char('가'));
+/// assert_eq!(Some(0xd065), encode_char('한'));
+/// assert_eq!(Some(0x8841), encode_char('ㄱ'));
+/// assert_eq!(Some(0x8444), encode_char('ㄳ'));
+/// assert_eq!(None, encode_char('a'));
+/// ```
+pub fn encode_char(c: char) -> Option<u16> {
+    if let Some((lead, vowel, tail)) = decompose(c) {
+        return Some(code(lead as u16 + 2, VOWEL_CODES[vowel as usize], TAIL_CODES[tail as usize]));
+    }
+    if blocks::block(c) != Some(blocks::Block::Compatibility) {
+        return None;
+    }
+    if let Some(lead) = blocks::index(c, Position::Lead) {
+        return Some(code(lead as u16 + 2, VOWEL_FILL, TAIL_FILL));
+    }
+    if let Some(vowel) = blocks::index(c, Position::Vowel) {
+        return Some(code(LEAD_FILL, VOWEL_CODES[vowel], TAIL_FILL));
+    }
+    let tail = blocks::index(c, Position::Tail)?;
+    Some(code(LEAD_FILL, VOWEL_FILL, TAIL_CODES[tail]))
+}
+
+/// Returns the syllable or compatibility jamo of a two-byte Johab code.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::johab::decode_char;
+/// assert_eq!(Some('한'), decode_char(0xd065));
+/// assert_eq!(Some('ㅏ'), decode_char(0x8461));
+/// assert_eq!(None, decode_char(0x8000));
+/// ```
+pub fn decode_char(code: u16) -> Option<char> {
+    if code & 0x8000 == 0 {
+        return None;
+    }
+    let (lead, vowel, tail) = (code >> 10 & 0x1f, code >> 5 & 0x1f, code & 0x1f);
+    let lead = match lead {
+        LEAD_FILL => None,
+        2..=20 => Some(lead as u32 - 2),
+        _ => return None,
+    };
+    let vowel = match vowel {
+        VOWEL_FILL => None,
+        _ => Some(VOWEL_CODES.iter().position(|&v| v == vowel)? as u32),
+    };
+    let tail = TAIL_CODES.iter().position(|&t| t == tail)? as u32;
+    let compat = |conjoining: u32| char::from_u32(conjoining).and_then(blocks::to_compatibility);
+    match (lead, vowel, tail) {
+        (Some(lead), Some(vowel), tail) => blocks::compose_indices(lead as usize, vowel as usize, tail as usize).ok(),
+        (Some(lead), None, 0) => compat(0x1100 + lead),
+        (None, Some(vowel), 0) => compat(0x1161 + vowel),
+        (None, None, tail) if tail > 0 => compat(0x11a7 + tail),
+        _ => None,
+    }
+}
+
+/// Encodes `text` as Johab, with ASCII as single bytes. Returns the bytes
+/// and whether any char had no Johab code and was written as `?`.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::johab::encode;
+/// assert_eq!((vec![0xd0, 0x65, b'!'], false), encode("한!"));
+/// assert_eq!((vec![b'?'], true), encode("漢"));
+/// ```
+pub fn encode(text: &str) -> (Vec<u8>, bool) {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut had_errors = false;
+    for c in text.chars() {
+        match encode_char(c) {
+            _ if c.is_ascii() => bytes.push(c as u8),
+            Some(code) => bytes.extend_from_slice(&code.to_be_bytes()),
+            None => {
+                bytes.push(b'?');
+                had_errors = true;
+            }
+        }
+    }
+    (bytes, had_errors)
+}
+
+/// Decodes Johab `bytes`, with bytes below 0x80 read as ASCII. Returns the
+/// text and whether any code was not a Hangul syllable or jamo and was
+/// replaced with U+FFFD; Hanja and symbols are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// use jamo::johab::decode;
+/// let (text, had_errors) = decode(&[0x88, 0x65, b' ', 0x8b, 0xb1]);
+/// assert_eq!(("간 김", false), (text.as_str(), had_errors));
+/// assert_eq!("gan gim", KoreanSentence::new(&text).roman());
+/// assert_eq!(("\u{fffd}".to_string(), true), decode(&[0x88]));
+/// ```
+pub fn decode(bytes: &[u8]) -> (String, bool) {
+    let mut text = String::with_capacity(bytes.len());
+    let mut had_errors = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] < 0x80 {
+            text.push(bytes[i] as char);
+            i += 1;
+            continue;
+        }
+        let c = bytes.get(i + 1).and_then(|&low| decode_char(u16::from_be_bytes([bytes[i], low])));
+        match c {
+            Some(c) => {
+                text.push(c);
+                i += 2;
+            }
+            None => {
+                text.push(char::REPLACEMENT_CHARACTER);
+                had_errors = true;
+                i += if i + 1 < bytes.len() { 2 } else { 1 };
+            }
+        }
+    }
+    (text, had_errors)
+}
@@ -0,0 +1,166 @@
+use crate::hangul::Letter;
+
+/// A particle (조사), with its forms after a syllable with and without a tail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Particle {
+    pub after_consonant: &'static str,
+    pub after_vowel: &'static str,
+}
+
+const fn particle(after_consonant: &'static str, after_vowel: &'static str) -> Particle {
+    Particle { after_consonant, after_vowel }
+}
+
+/// Common particles. Those with a single form have it in both fields.
+pub const PARTICLES: [Particle; 17] = [
+    particle("은", "는"), particle("이", "가"), particle("을", "를"), particle("과", "와"),
+    particle("으로", "로"), particle("의", "의"), particle("에", "에"), particle("에서", "에서"),
+    particle("에게", "에게"), particle("께서", "께서"), particle("한테", "한테"),
+    particle("도", "도"), particle("만", "만"), particle("까지", "까지"), particle("부터", "부터"),
+    particle("보다", "보다"), particle("처럼", "처럼"), ];
+
+impl Particle {
+    /// Returns the form that follows `word`. ㄹ takes the vowel form of 으로.
+    /// Words not ending in a Hangul syllable get the consonant form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::josa::{Particle, PARTICLES};
+    /// let euro = PARTICLES.iter().find(|p| p.after_vowel == "로").unwrap();
+    /// assert_eq!("으로", euro.form("집"));
+    /// assert_eq!("로", euro.form("학교"));
+    /// assert_eq!("로", euro.form("서울"));
+    /// ```
+    pub fn form(&self, word: &str) -> &'static str {
+        match word.chars().last().map(Letter::new) {
+            Some(Letter::HangulLetter(h)) => match h.tail().compat_char() {
+                None => self.after_vowel,
+                Some('ㄹ') if self.after_consonant == "으로" => self.after_vowel,
+                Some(_) => self.after_consonant,
+            },
+            _ => self.after_consonant,
+        }
+    }
+}
+
+/// Splits a trailing particle off `word` if one agrees with the syllable
+/// before it, returning the stem and the particle.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::josa::split_particle;
+/// assert_eq!(Some(("학교", "에서")), split_particle("학교에서"));
+/// assert_eq!(Some(("책", "을")), split_particle("책을"));
+/// assert_eq!(None, split_particle("나이")); // 이 does not follow a vowel
+/// ```
+pub fn split_particle(word: &str) -> Option<(&str, &str)> {
+    PARTICLES.iter()
+        .flat_map(|p| vec![(p, p.after_consonant), (p, p.after_vowel)])
+        .filter(|(_, form)| word.len() > form.len() && word.ends_with(form))
+        .filter(|(p, form)| p.form(&word[..word.len() - form.len()]) == *form)
+        .max_by_key(|(_, form)| form.len())
+        .map(|(_, form)| word.split_at(word.len() - form.len()))
+}
+
+/// Fills `{name}` placeholders in `template` from `vars`, choosing the form
+/// of a particle written right after a placeholder to agree with the value:
+/// either both forms split by a slash (이/가, 을/를) or the optional part in
+/// parentheses ((으)로, (이)나). Unknown placeholders are left as they are and
+/// `{{` is a literal brace.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::josa::fill;
+/// let template = "{name}이/가 {place}(으)로 왔습니다";
+/// assert_eq!("철수가 서울로 왔습니다", fill(template, &[("name", "철수"), ("place", "서울")]));
+/// assert_eq!("민준이 집으로 왔습니다", fill(template, &[("name", "민준"), ("place", "집")]));
+/// assert_eq!("사과를 {count}개 샀다", fill("{item}을/를 {count}개 샀다", &[("item", "사과")]));
+/// assert_eq!("{x}는 책이랑", fill("{{x}는 {y}(이)랑", &[("y", "책")]));
+/// assert_eq!("나랑 너랑", fill("{a}이랑/랑 {b}랑/이랑", &[("a", "나"), ("b", "너")]));
+/// assert_eq!("철수와의 약속", fill("{name}와/과의 약속", &[("name", "철수")]));
+/// assert_eq!("민준과의 약속", fill("{name}와/과의 약속", &[("name", "민준")]));
+/// ```
+pub fn fill<K: AsRef<str>, V: AsRef<str>>(template: &str, vars: &[(K, V)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        filled.push_str(&rest[..open]);
+        rest = &rest[open..];
+        if let Some(after) = rest.strip_prefix("{{") {
+            filled.push('{');
+            rest = after;
+            continue;
+        }
+        let close = match rest.find('}') {
+            Some(close) => close,
+            None => break,
+        };
+        let value = vars.iter().find(|(k, _)| k.as_ref() == &rest[1..close]).map(|(_, v)| v.as_ref());
+        match value {
+            Some(value) => {
+                filled.push_str(value);
+                let (particle, used) = agreeing_particle(value, &rest[close + 1..]);
+                filled.push_str(particle);
+                rest = &rest[close + 1 + used..];
+            }
+            None => {
+                filled.push_str(&rest[..=close]);
+                rest = &rest[close + 1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+/// Returns the form of the particle alternation at the start of `rest` that
+/// follows `word`, and the bytes of `rest` it replaces.
+fn agreeing_particle<'a>(word: &str, rest: &'a str) -> (&'a str, usize) {
+    let syllables = |s: &str| s.find(|c| !matches!(Letter::new(c), Letter::HangulLetter(_))).unwrap_or(s.len());
+    if let Some(inner) = rest.strip_prefix('(') {
+        let optional = &inner[..syllables(inner)];
+        if optional.is_empty() || !inner[optional.len()..].starts_with(')') {
+            return ("", 0);
+        }
+        let euro = optional == "으" && inner[optional.len() + 1..].starts_with('로');
+        let consonant = match euro {
+            true => particle("으로", "로").form(word) == "으로",
+            false => particle("이", "가").form(word) == "이",
+        };
+        return (if consonant { optional } else { "" }, optional.len() + 2);
+    }
+    let first = &rest[..syllables(rest)];
+    let after = match rest[first.len()..].strip_prefix('/') {
+        Some(after) if !first.is_empty() => after,
+        _ => return ("", 0),
+    };
+    // The second form ends where a known form does; text after it is kept.
+    let known = PARTICLES.iter()
+        .filter_map(|p| match (p.after_consonant, p.after_vowel) {
+            (c, v) if c == first => Some((p, v)),
+            (c, v) if v == first => Some((p, c)),
+            _ => None,
+        })
+        .filter(|(_, second)| after.starts_with(second))
+        .max_by_key(|(_, second)| second.len());
+    if let Some((p, second)) = known {
+        return (p.form(word), first.len() + 1 + second.len());
+    }
+    // An unknown pair, told apart by a leading 이 or 으 on the form after a
+    // consonant (이랑/랑, 이나/나).
+    let unlinked = |s: &'a str| s.strip_prefix('이').or_else(|| s.strip_prefix('으'));
+    let second = match unlinked(first) {
+        Some(short) if !short.is_empty() && after.starts_with(short) => &after[..short.len()],
+        _ if unlinked(after).is_some_and(|short| short.starts_with(first)) => &after[..'이'.len_utf8() + first.len()],
+        _ => return ("", 0),
+    };
+    let used = first.len() + 1 + second.len();
+    let (after_consonant, after_vowel) = if first.len() > second.len() { (first, second) } else { (second, first) };
+    match particle("이", "가").form(word) {
+        "이" => (after_consonant, used),
+        _ => (after_vowel, used),
+    }
+}
@@ -0,0 +1,71 @@
+//! The JSON form of a processed sentence, shared by
+//! [`KoreanSentence::to_json`] and the `--format json` output of the binary.
+//!
+//! The schema is versioned by [`SCHEMA_VERSION`]. Fields are only ever added
+//! within a version; renaming, removing or changing the meaning of a field
+//! bumps it.
+//!
+//! ```text
+//! {
+//!   "schema_version": 1,
+//!   "text": "좋아",                  // the source text
+//!   "roman": "joa",                 // romanization of the sentence as it is
+//!   "pronounced": "조아",            // the syllables as they are
+//!   "records": [                    // one per letter, see SyllableRecord
+//!     {"char_index": 0, "byte_range": [0, 3], "original": "좋",
+//!      "lead": "ㅈ", "vowel": "ㅗ", "tail": null, "roman": "jo"},
+//!     ...
+//!   ],
+//!   "rules": [                      // rules that fired, in order
+//!     {"rule": "h-deletion", "letters": [0, 1]}
+//!   ]
+//! }
+//! ```
+//!
+//! [`KoreanSentence::to_json`]: crate::hangul::KoreanSentence::to_json
+//! [`SyllableRecord`]: crate::hangul::SyllableRecord
+
+use crate::hangul::KoreanSentence;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+pub(crate) fn to_json(sentence: &KoreanSentence) -> String {
+    let records = sentence.records();
+    let text = records.iter().map(|r| r.original).collect::<String>();
+    let records = records.iter().map(|r| format!(
+        r#"{{"char_index":{},"byte_range":[{},{}],"original":{},"lead":{},"vowel":{},"tail":{},"roman":{}}}"#,
+        r.char_index, r.byte_range.start, r.byte_range.end, string(&r.original.to_string()),
+        jamo(r.lead), jamo(r.vowel), jamo(r.tail), string(&r.roman),
+    )).collect::<Vec<String>>();
+    let rules = sentence.fired_rules().iter().map(|f| format!(
+        r#"{{"rule":{},"letters":[{},{}]}}"#, string(&f.rule), f.index, f.index + 1,
+    )).collect::<Vec<String>>();
+    format!(
+        r#"{{"schema_version":{},"text":{},"roman":{},"pronounced":{},"records":[{}],"rules":[{}]}}"#,
+        SCHEMA_VERSION, string(&text), string(&sentence.roman()), string(&sentence.pronounced()),
+        records.join(","), rules.join(","),
+    )
+}
+
+fn jamo(c: Option<char>) -> String {
+    c.map_or("null".to_string(), |c| string(&c.to_string()))
+}
+
+/// Returns `s` as a JSON string literal.
+fn string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
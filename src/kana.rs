@@ -0,0 +1,111 @@
+use crate::hangul::{Hangul, KoreanSentence, Letter};
+
+struct Row {
+    /// Kana for the a, i, u, e, o columns.
+    cols: [&'static str; 5],
+    /// Kana taking a small ャ/ュ/ェ/ョ for y-diphthongs.
+    y: &'static str,
+    /// Kana taking a small ァ/ィ/ェ/ォ for w-diphthongs.
+    w: &'static str,
+    /// The row is already palatal (チャ), so y-diphthongs use the plain columns.
+    palatal: bool,
+}
+
+const fn row(cols: [&'static str; 5], y: &'static str, w: &'static str) -> Row {
+    Row { cols, y, w, palatal: false }
+}
+
+const K: Row = row(["カ", "キ", "ク", "ケ", "コ"], "キ", "ク");
+const G: Row = row(["ガ", "ギ", "グ", "ゲ", "ゴ"], "ギ", "グ");
+const N: Row = row(["ナ", "ニ", "ヌ", "ネ", "ノ"], "ニ", "ヌ");
+const T: Row = row(["タ", "ティ", "トゥ", "テ", "ト"], "テ", "ト");
+const D: Row = row(["ダ", "ディ", "ドゥ", "デ", "ド"], "デ", "ド");
+const R: Row = row(["ラ", "リ", "ル", "レ", "ロ"], "リ", "ル");
+const M: Row = row(["マ", "ミ", "ム", "メ", "モ"], "ミ", "ム");
+const P: Row = row(["パ", "ピ", "プ", "ペ", "ポ"], "ピ", "プ");
+const B: Row = row(["バ", "ビ", "ブ", "ベ", "ボ"], "ビ", "ブ");
+const S: Row = row(["サ", "シ", "ス", "セ", "ソ"], "シ", "ス");
+const H: Row = row(["ハ", "ヒ", "フ", "ヘ", "ホ"], "ヒ", "フ");
+const CH: Row = Row { cols: ["チャ", "チ", "チュ", "チェ", "チョ"], y: "チ", w: "チュ", palatal: true };
+const J: Row = Row { cols: ["ジャ", "ジ", "ジュ", "ジェ", "ジョ"], y: "ジ", w: "ジュ", palatal: true };
+const VOWEL: Row = row(["ア", "イ", "ウ", "エ", "オ"], "", "");
+
+// Rows by lead index, and the voiced row a plain lead takes after a vowel or
+// a sonorant tail (감사합니다 → カムサハムニダ).
+const LEAD_ROWS: [&Row; 19] = [
+    &K, &K, &N, &T, &T, &R, &M, &P, &P, &S, &S, &VOWEL, &CH, &CH, &CH, &K, &T, &P, &H, ];
+const VOICED_ROWS: [Option<&Row>; 19] = [
+    Some(&G), None, None, Some(&D), None, None, None, Some(&B), None, None,
+    None, None, Some(&J), None, None, None, None, None, None, ];
+
+#[derive(Clone, Copy)]
+enum Glide {
+    None,
+    Y,
+    W,
+}
+
+const A: usize = 0;
+const I: usize = 1;
+const U: usize = 2;
+const E: usize = 3;
+const O: usize = 4;
+
+const VOWELS: [(Glide, usize); 21] = [
+    (Glide::None, A), (Glide::None, E), (Glide::Y, A), (Glide::Y, E), (Glide::None, O),
+    (Glide::None, E), (Glide::Y, O), (Glide::Y, E), (Glide::None, O), (Glide::W, A),
+    (Glide::W, E), (Glide::W, E), (Glide::Y, O), (Glide::None, U), (Glide::W, O),
+    (Glide::W, E), (Glide::W, I), (Glide::Y, U), (Glide::None, U), (Glide::None, I),
+    (Glide::None, I), ];
+const SMALL_Y: [&str; 5] = ["ャ", "", "ュ", "ェ", "ョ"];
+const SMALL_W: [&str; 5] = ["ァ", "ィ", "", "ェ", "ォ"];
+const INITIAL_Y: [&str; 5] = ["ヤ", "イ", "ユ", "イェ", "ヨ"];
+const INITIAL_W: [&str; 5] = ["ワ", "ウィ", "ウ", "ウェ", "ウォ"];
+
+// Tails neutralize to the seven pronounced codas.
+const TAILS: [&str; 28] = [
+    "", "ク", "ク", "ク", "ン", "ン", "ン", "ッ", "ル", "ク",
+    "ム", "ル", "ル", "ル", "プ", "ル", "ム", "プ", "プ", "ッ",
+    "ッ", "ン", "ッ", "ッ", "ク", "ッ", "プ", "ッ", ];
+const SONORANT_TAILS: [usize; 4] = [4, 8, 16, 21];
+
+/// Transliterates the letters of `sentence` into katakana as they are,
+/// without applying rules. See [`KoreanSentence::kana`].
+pub fn kana(sentence: &KoreanSentence) -> String {
+    let mut result = String::new();
+    let mut voicing = false;
+    for letter in sentence.letters() {
+        match letter {
+            Letter::HangulLetter(h) => {
+                result.push_str(&syllable(h, voicing));
+                voicing = h.tail().index() == 0 || SONORANT_TAILS.contains(&h.tail().index());
+            }
+            Letter::OtherLetter(c) => {
+                result.push(*c);
+                voicing = false;
+            }
+        }
+    }
+    result
+}
+
+fn syllable(h: &Hangul, voicing: bool) -> String {
+    let lead = h.lead().index();
+    let row = match VOICED_ROWS[lead] {
+        Some(voiced) if voicing => voiced,
+        _ => LEAD_ROWS[lead],
+    };
+    let is_vowel_row = row.cols == VOWEL.cols;
+    let (glide, col) = VOWELS[h.vowel().index()];
+    let body = match glide {
+        // ㅢ is written out as two vowels on its own and reduces to ㅣ after a consonant.
+        Glide::None if h.vowel().index() == 19 && is_vowel_row => "ウイ".to_string(),
+        Glide::None => row.cols[col].to_string(),
+        Glide::Y if row.palatal => row.cols[col].to_string(),
+        Glide::Y if is_vowel_row => INITIAL_Y[col].to_string(),
+        Glide::Y => format!("{}{}", row.y, SMALL_Y[col]),
+        Glide::W if is_vowel_row => INITIAL_W[col].to_string(),
+        Glide::W => format!("{}{}", row.w, SMALL_W[col]),
+    };
+    format!("{}{}", body, TAILS[h.tail().index()])
+}
@@ -0,0 +1,234 @@
+use crate::hangul::{Jamo, KoreanSentence, Letter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// The standard 2-set layout (두벌식, KS X 5002).
+    Dubeolsik,
+    /// The 3-set 390 layout (세벌식 390), with separate lead, vowel and tail keys.
+    Sebeolsik390,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+    Thumb,
+}
+
+const FINGERS: [Finger; 9] = [
+    Finger::LeftPinky, Finger::LeftRing, Finger::LeftMiddle, Finger::LeftIndex,
+    Finger::RightIndex, Finger::RightMiddle, Finger::RightRing, Finger::RightPinky,
+    Finger::Thumb, ];
+
+// Jamo and the character their key produces on a US keyboard; an uppercase
+// letter or shifted symbol means the key is pressed with shift.
+const DUBEOLSIK: [(char, char); 33] = [
+    ('ㅂ', 'q'), ('ㅈ', 'w'), ('ㄷ', 'e'), ('ㄱ', 'r'), ('ㅅ', 't'),
+    ('ㅛ', 'y'), ('ㅕ', 'u'), ('ㅑ', 'i'), ('ㅐ', 'o'), ('ㅔ', 'p'),
+    ('ㅁ', 'a'), ('ㄴ', 's'), ('ㅇ', 'd'), ('ㄹ', 'f'), ('ㅎ', 'g'),
+    ('ㅗ', 'h'), ('ㅓ', 'j'), ('ㅏ', 'k'), ('ㅣ', 'l'),
+    ('ㅋ', 'z'), ('ㅌ', 'x'), ('ㅊ', 'c'), ('ㅍ', 'v'), ('ㅠ', 'b'), ('ㅜ', 'n'), ('ㅡ', 'm'),
+    ('ㅃ', 'Q'), ('ㅉ', 'W'), ('ㄸ', 'E'), ('ㄲ', 'R'), ('ㅆ', 'T'), ('ㅒ', 'O'), ('ㅖ', 'P'), ];
+const SEBEOLSIK_LEADS: [(char, &str); 19] = [
+    ('ㄱ', "k"), ('ㄲ', "kk"), ('ㄴ', "h"), ('ㄷ', "u"), ('ㄸ', "uu"), ('ㄹ', "y"), ('ㅁ', "i"),
+    ('ㅂ', ";"), ('ㅃ', ";;"), ('ㅅ', "n"), ('ㅆ', "nn"), ('ㅇ', "j"), ('ㅈ', "l"), ('ㅉ', "ll"),
+    ('ㅊ', "o"), ('ㅋ', "0"), ('ㅌ', "'"), ('ㅍ', "p"), ('ㅎ', "m"), ];
+const SEBEOLSIK_VOWELS: [(char, &str); 21] = [
+    ('ㅏ', "f"), ('ㅐ', "r"), ('ㅑ', "6"), ('ㅒ', "G"), ('ㅓ', "t"), ('ㅔ', "c"), ('ㅕ', "e"),
+    ('ㅖ', "7"), ('ㅗ', "v"), ('ㅘ', "/f"), ('ㅙ', "/r"), ('ㅚ', "/d"), ('ㅛ', "4"), ('ㅜ', "b"),
+    ('ㅝ', "9t"), ('ㅞ', "9c"), ('ㅟ', "9d"), ('ㅠ', "5"), ('ㅡ', "g"), ('ㅢ', "8"), ('ㅣ', "d"), ];
+const SEBEOLSIK_TAILS: [(char, &str); 27] = [
+    ('ㄱ', "x"), ('ㄲ', "!"), ('ㄳ', "V"), ('ㄴ', "s"), ('ㄵ', "E"), ('ㄶ', "S"), ('ㄷ', "A"),
+    ('ㄹ', "w"), ('ㄺ', "@"), ('ㄻ', "F"), ('ㄼ', "D"), ('ㄽ', "T"), ('ㄾ', "%"), ('ㄿ', "$"),
+    ('ㅀ', "R"), ('ㅁ', "z"), ('ㅂ', "3"), ('ㅄ', "X"), ('ㅅ', "q"), ('ㅆ', "2"), ('ㅇ', "a"),
+    ('ㅈ', "#"), ('ㅊ', "Z"), ('ㅋ', "C"), ('ㅌ', "W"), ('ㅍ', "Q"), ('ㅎ', "1"), ];
+
+const SHIFTED_SYMBOLS: &str = "~!@#$%^&*()_+{}|:\"<>?";
+const UNSHIFTED_SYMBOLS: &str = "`1234567890-=[]\\;',./";
+
+/// A key press on a US keyboard, named by the character the key produces
+/// without shift.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub key: char,
+    pub shift: bool,
+}
+
+/// Keystrokes per finger for typing a text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FingerLoad {
+    counts: [usize; 9],
+}
+
+impl FingerLoad {
+    pub fn get(&self, finger: Finger) -> usize {
+        self.counts[finger as usize]
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the share of all keystrokes made by `finger`, or 0 when empty.
+    pub fn ratio(&self, finger: Finger) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.get(finger) as f64 / total as f64,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Finger, usize)> + '_ {
+        FINGERS.iter().map(move |&f| (f, self.get(f)))
+    }
+}
+
+/// Returns the number of key presses needed to type `text`, counting each
+/// shift press. Characters the layout cannot type are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::keyboard::{keystroke_count, Layout};
+/// assert_eq!(6, keystroke_count("한글", Layout::Dubeolsik));
+/// assert_eq!(8, keystroke_count("빵 와", Layout::Dubeolsik));
+/// assert_eq!(3, keystroke_count("까", Layout::Sebeolsik390));
+/// ```
+pub fn keystroke_count(text: &str, layout: Layout) -> usize {
+    key_events(text, layout).iter().map(|e| if e.shift { 2 } else { 1 }).sum()
+}
+
+/// Returns how many keystrokes each finger makes when touch-typing `text`.
+/// Shift is pressed by the pinky of the other hand.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::keyboard::{finger_load, Finger, Layout};
+/// let load = finger_load("ㅃ", Layout::Dubeolsik);
+/// assert_eq!(1, load.get(Finger::LeftPinky));
+/// assert_eq!(1, load.get(Finger::RightPinky));
+/// ```
+pub fn finger_load(text: &str, layout: Layout) -> FingerLoad {
+    let mut load = FingerLoad::default();
+    for event in key_events(text, layout) {
+        let finger = finger(event.key);
+        load.counts[finger as usize] += 1;
+        if event.shift {
+            let shift_finger = match finger {
+                Finger::LeftPinky | Finger::LeftRing | Finger::LeftMiddle | Finger::LeftIndex =>
+                    Finger::RightPinky,
+                _ => Finger::LeftPinky,
+            };
+            load.counts[shift_finger as usize] += 1;
+        }
+    }
+    load
+}
+
+/// Returns the key presses a typist must make, in order, to type `text`.
+/// Tense consonants are typed with shift on the 2-set layout and by pressing
+/// the plain lead twice on the 3-set layout.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::keyboard::{key_events, KeyEvent, Layout};
+/// let events = key_events("빼", Layout::Dubeolsik);
+/// assert_eq!(vec![KeyEvent { key: 'q', shift: true }, KeyEvent { key: 'o', shift: false }], events);
+/// let keys = key_events("빼", Layout::Sebeolsik390).iter().map(|e| e.key).collect::<String>();
+/// assert_eq!(";;r", keys);
+/// ```
+pub fn key_events(text: &str, layout: Layout) -> Vec<KeyEvent> {
+    let mut produced = String::new();
+    for letter in KoreanSentence::new(text).letters() {
+        match (layout, letter) {
+            (Layout::Sebeolsik390, Letter::HangulLetter(h)) => {
+                produced.push_str(sebeolsik(h.lead(), &SEBEOLSIK_LEADS));
+                produced.push_str(sebeolsik(h.vowel(), &SEBEOLSIK_VOWELS));
+                produced.push_str(sebeolsik(h.tail(), &SEBEOLSIK_TAILS));
+            }
+            (Layout::Sebeolsik390, Letter::OtherLetter(c)) => {
+                let mut table = SEBEOLSIK_LEADS.iter().chain(SEBEOLSIK_VOWELS.iter());
+                match table.find(|(j, _)| j == c) {
+                    Some((_, keys)) => produced.push_str(keys),
+                    None => produced.push(*c),
+                }
+            }
+            (Layout::Dubeolsik, _) => {
+                for c in letter.strokes().chars() {
+                    produced.push(DUBEOLSIK.iter().find(|(j, _)| *j == c).map_or(c, |&(_, k)| k));
+                }
+            }
+        }
+    }
+    produced.chars().filter_map(physical_key).collect()
+}
+
+fn sebeolsik(jamo: &Jamo, table: &[(char, &'static str)]) -> &'static str {
+    jamo.compat_char()
+        .and_then(|c| table.iter().find(|(j, _)| *j == c))
+        .map_or("", |&(_, keys)| keys)
+}
+
+fn physical_key(c: char) -> Option<KeyEvent> {
+    if c == ' ' || c.is_ascii_lowercase() || c.is_ascii_digit() || UNSHIFTED_SYMBOLS.contains(c) {
+        Some(KeyEvent { key: c, shift: false })
+    } else if c.is_ascii_uppercase() {
+        Some(KeyEvent { key: c.to_ascii_lowercase(), shift: true })
+    } else {
+        SHIFTED_SYMBOLS.chars().position(|s| s == c)
+            .and_then(|i| UNSHIFTED_SYMBOLS.chars().nth(i))
+            .map(|key| KeyEvent { key, shift: true })
+    }
+}
+
+fn finger(key: char) -> Finger {
+    match key {
+        '`' | '1' | 'q' | 'a' | 'z' => Finger::LeftPinky,
+        '2' | 'w' | 's' | 'x' => Finger::LeftRing,
+        '3' | 'e' | 'd' | 'c' => Finger::LeftMiddle,
+        '4' | '5' | 'r' | 't' | 'f' | 'g' | 'v' | 'b' => Finger::LeftIndex,
+        '6' | '7' | 'y' | 'u' | 'h' | 'j' | 'n' | 'm' => Finger::RightIndex,
+        '8' | 'i' | 'k' | ',' => Finger::RightMiddle,
+        '9' | 'o' | 'l' | '.' => Finger::RightRing,
+        ' ' => Finger::Thumb,
+        _ => Finger::RightPinky,
+    }
+}
+
+// The letter keys of a US keyboard by row, each row shifted half a key right
+// of the one above.
+const LETTER_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+type JamoPairs = Vec<(char, char)>;
+
+/// Returns the pairs of 2-set jamo on touching keys, and the pairs typed on
+/// the same key with and without shift (ㄱ/ㄲ, ㅐ/ㅒ).
+pub(crate) fn dubeolsik_neighbors() -> (JamoPairs, JamoPairs) {
+    let jamo = |key: char| DUBEOLSIK.iter().find(|&&(_, k)| k == key).map(|&(j, _)| j);
+    let mut adjacent = vec![];
+    for (r, row) in LETTER_ROWS.iter().enumerate() {
+        for (c, key) in row.chars().enumerate() {
+            let mut touching = row.chars().skip(c + 1).take(1).collect::<Vec<char>>();
+            if let Some(below) = LETTER_ROWS.get(r + 1) {
+                touching.extend(below.chars().skip(c.saturating_sub(1)).take(if c == 0 { 1 } else { 2 }));
+            }
+            for other in touching {
+                if let (Some(a), Some(b)) = (jamo(key), jamo(other)) {
+                    adjacent.push((a, b));
+                }
+            }
+        }
+    }
+    let shifted = DUBEOLSIK.iter()
+        .filter(|(_, k)| k.is_ascii_uppercase())
+        .filter_map(|&(j, k)| jamo(k.to_ascii_lowercase()).map(|plain| (plain, j)))
+        .collect();
+    (adjacent, shifted)
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::hangul::KoreanSentence;
+
+/// An exception to the rules: a surface form with its pronunciation and/or
+/// romanization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LexiconEntry {
+    pub surface: String,
+    /// The pronunciation in Hangul, romanized without rules when `roman` is absent.
+    pub pronunciation: Option<String>,
+    pub roman: Option<String>,
+}
+
+/// User exceptions consulted before the rules, such as brand and place names.
+#[derive(Default)]
+pub struct Lexicon {
+    entries: HashMap<String, LexiconEntry>,
+}
+
+impl Lexicon {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a lexicon from tab-separated lines of surface, pronunciation
+    /// and romanization. Trailing fields may be left out or empty; blank
+    /// lines and lines starting with `#` are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::lexicon::Lexicon;
+    /// let lexicon = Lexicon::from_tsv("# surface\tpronunciation\troman\n신라\t실라\tSilla\n같이\t가치\n");
+    /// assert_eq!("Sillawa gachi", lexicon.roman("신라와 같이"));
+    /// ```
+    pub fn from_tsv(s: &str) -> Self {
+        let mut lexicon = Self::new();
+        for line in s.lines().filter(|l| !l.trim().is_empty() && !l.starts_with('#')) {
+            let mut fields = line.split('\t').map(str::trim);
+            let surface = fields.next().unwrap_or("").to_string();
+            let mut optional = || fields.next().filter(|f| !f.is_empty()).map(String::from);
+            let (pronunciation, roman) = (optional(), optional());
+            lexicon.insert(LexiconEntry { surface, pronunciation, roman });
+        }
+        lexicon
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::from_tsv(&fs::read_to_string(path)?))
+    }
+
+    /// Adds an entry, replacing any entry with the same surface.
+    pub fn insert(&mut self, entry: LexiconEntry) {
+        self.entries.insert(entry.surface.clone(), entry);
+    }
+
+    pub fn get(&self, surface: &str) -> Option<&LexiconEntry> {
+        self.entries.get(surface)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Romanizes `text` with the rules applied, except for words starting
+    /// with a lexicon surface, which take the entry's form. The rest of such
+    /// a word, e.g. a particle, goes through the rules as usual.
+    pub fn roman(&self, text: &str) -> String {
+        self.convert(text, |entry| match (&entry.roman, &entry.pronunciation) {
+            (Some(roman), _) => roman.clone(),
+            (None, Some(pronunciation)) => KoreanSentence::new(pronunciation).roman(),
+            (None, None) => KoreanSentence::new(&entry.surface).applied().roman(),
+        }, |s| s.applied().roman())
+    }
+
+    /// Returns the pronounced form of `text`, taking pronunciations from the
+    /// lexicon where it has them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::lexicon::Lexicon;
+    /// let lexicon = Lexicon::from_tsv("신라\t실라\n");
+    /// assert_eq!("실라와 조아요", lexicon.pronounced("신라와 좋아요"));
+    /// ```
+    pub fn pronounced(&self, text: &str) -> String {
+        self.convert(text, |entry| match &entry.pronunciation {
+            Some(pronunciation) => pronunciation.clone(),
+            None => KoreanSentence::new(&entry.surface).pronounced(),
+        }, |s| s.pronounced())
+    }
+
+    fn convert<E, R>(&self, text: &str, entry_form: E, rule_form: R) -> String
+        where E: Fn(&LexiconEntry) -> String, R: Fn(&KoreanSentence) -> String {
+        let mut result = String::new();
+        for word in text.split_inclusive(char::is_whitespace) {
+            let prefix = word.char_indices().map(|(i, _)| i).chain(Some(word.len()))
+                .rev()
+                .find_map(|end| self.entries.get(&word[..end]).map(|entry| (end, entry)));
+            match prefix {
+                Some((end, entry)) if end > 0 => {
+                    result.push_str(&entry_form(entry));
+                    result.push_str(&rule_form(&KoreanSentence::new(&word[end..])));
+                }
+                _ => result.push_str(&rule_form(&KoreanSentence::new(word))),
+            }
+        }
+        result
+    }
+}
@@ -1 +1,121 @@
-pub mod hangul;
\ No newline at end of file
+//! Hangul decomposition, romanization and pronunciation rules.
+//!
+//! # Panics
+//!
+//! No public function panics, whatever its input: out-of-range arguments are
+//! clamped and lookups that cannot succeed fall back to a documented default.
+//! Only callbacks supplied by the caller can panic. Should a worker thread of
+//! [`batch`] panic all the same, [`batch::convert_file_with`] fails with an
+//! `io::Error` and the corpus iterators resume the worker's panic unchanged.
+//! [`batch::process_any`] runs the whole pipeline on arbitrary bytes for fuzzing.
+
+pub mod analysis;
+pub mod anagram;
+pub mod batch;
+#[cfg(feature = "binary")]
+pub mod binary;
+pub mod blocks;
+#[cfg(feature = "color")]
+pub mod color;
+pub mod diff;
+pub mod gen;
+pub mod hangul;
+pub mod johab;
+pub mod josa;
+pub mod json;
+pub mod kana;
+pub mod keyboard;
+pub mod lexicon;
+pub mod loanword;
+mod macros;
+pub mod numeral;
+pub mod pattern;
+pub mod phonology;
+pub mod politeness;
+#[cfg(feature = "proto")]
+pub mod proto;
+pub mod roman;
+pub mod rules;
+pub mod sanitize;
+pub mod search;
+pub mod security;
+pub mod segment;
+pub mod spell;
+pub mod stt;
+pub mod table;
+pub mod trie;
+
+use std::convert::TryFrom;
+use std::sync::OnceLock;
+
+use crate::hangul::{Hangul, KoreanSentence, Letter};
+
+/// Returns the romanization of `text` after the standard pronunciation rules.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!("joayo annyeong", jamo::romanize("좋아요 안녕"));
+/// ```
+pub fn romanize(text: &str) -> String {
+    KoreanSentence::new(text).applied().roman()
+}
+
+/// Returns `text` as it is pronounced under the standard rules, in Hangul.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!("조아요", jamo::pronounce("좋아요"));
+/// ```
+pub fn pronounce(text: &str) -> String {
+    KoreanSentence::new(text).pronounced()
+}
+
+/// Returns `text` with each syllable split into its lead, vowel and tail as
+/// compatibility jamo. Compound vowels and tails stay whole.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!("ㅎㅏㄴㄱㅘ a", jamo::decompose("한과 a"));
+/// ```
+pub fn decompose(text: &str) -> String {
+    KoreanSentence::new(text).letters().iter().flat_map(|letter| match letter {
+        Letter::HangulLetter(h) => vec![h.lead().compat_char(), h.vowel().compat_char(), h.tail().compat_char()],
+        Letter::OtherLetter(c) => vec![Some(*c)],
+    }).flatten().collect()
+}
+
+/// Returns the romanization of a single precomposed syllable, without rules,
+/// or `None` for any other char.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(Some("han"), jamo::romanize_char('한'));
+/// assert_eq!(None, jamo::romanize_char('a'));
+/// ```
+pub fn romanize_char(c: char) -> Option<&'static str> {
+    static SYLLABLES: OnceLock<Vec<String>> = OnceLock::new();
+    hangul::decompose(c)?;
+    let syllables = SYLLABLES.get_or_init(|| {
+        ('\u{ac00}'..='\u{d7a3}').filter_map(|c| Hangul::try_from(c).ok()).map(|h| h.roman_string()).collect()
+    });
+    syllables.get(c as usize - 0xac00).map(String::as_str)
+}
+
+/// Returns the lead, vowel and tail of a single precomposed syllable as
+/// compatibility jamo, or `None` for any other char.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(Some(('ㄷ', 'ㅏ', Some('ㄺ'))), jamo::decompose_char('닭'));
+/// assert_eq!(Some(('ㅇ', 'ㅏ', None)), jamo::decompose_char('아'));
+/// assert_eq!(None, jamo::decompose_char('ㄱ'));
+/// ```
+pub fn decompose_char(c: char) -> Option<(char, char, Option<char>)> {
+    let h = Hangul::try_from(c).ok()?;
+    Some((h.lead().compat_char()?, h.vowel().compat_char()?, h.tail().compat_char()))
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use crate::hangul::{Hangul, KoreanSentence, Letter};
+
+const BEAM_WIDTH: usize = 64;
+
+type Options = &'static [(&'static str, f64)];
+
+const LEAD_SPELLINGS: [Options; 19] = [
+    &[("g", 0.7), ("c", 0.2), ("k", 0.1)], // ㄱ
+    &[("k", 0.5), ("c", 0.3), ("g", 0.2)], // ㄲ
+    &[("n", 1.0)], // ㄴ
+    &[("d", 0.9), ("t", 0.1)], // ㄷ
+    &[("t", 0.7), ("d", 0.3)], // ㄸ
+    &[("r", 0.5), ("l", 0.5)], // ㄹ
+    &[("m", 1.0)], // ㅁ
+    &[("b", 0.8), ("v", 0.2)], // ㅂ
+    &[("p", 0.7), ("b", 0.3)], // ㅃ
+    &[("s", 0.7), ("c", 0.2), ("th", 0.1)], // ㅅ
+    &[("s", 0.8), ("ss", 0.2)], // ㅆ
+    &[("", 1.0)], // ㅇ
+    &[("j", 0.5), ("g", 0.3), ("z", 0.2)], // ㅈ
+    &[("z", 0.6), ("j", 0.4)], // ㅉ
+    &[("ch", 0.6), ("t", 0.2), ("c", 0.2)], // ㅊ
+    &[("c", 0.5), ("k", 0.4), ("q", 0.1)], // ㅋ
+    &[("t", 0.9), ("th", 0.1)], // ㅌ
+    &[("p", 0.6), ("f", 0.4)], // ㅍ
+    &[("h", 1.0)], // ㅎ
+];
+
+const VOWEL_SPELLINGS: [Options; 21] = [
+    &[("a", 0.8), ("u", 0.1), ("o", 0.1)], // ㅏ
+    &[("a", 0.6), ("e", 0.4)], // ㅐ
+    &[("ya", 0.5), ("ia", 0.5)], // ㅑ
+    &[("ya", 0.6), ("ia", 0.4)], // ㅒ
+    &[("o", 0.4), ("u", 0.3), ("e", 0.2), ("er", 0.1)], // ㅓ
+    &[("e", 0.9), ("ai", 0.1)], // ㅔ
+    &[("yo", 0.6), ("io", 0.4)], // ㅕ
+    &[("ye", 0.6), ("ie", 0.4)], // ㅖ
+    &[("o", 0.9), ("au", 0.1)], // ㅗ
+    &[("wa", 0.7), ("oa", 0.3)], // ㅘ
+    &[("wa", 0.6), ("wae", 0.4)], // ㅙ
+    &[("oe", 0.6), ("we", 0.4)], // ㅚ
+    &[("yo", 0.6), ("io", 0.4)], // ㅛ
+    &[("u", 0.6), ("oo", 0.3), ("o", 0.1)], // ㅜ
+    &[("wo", 0.7), ("uo", 0.3)], // ㅝ
+    &[("we", 0.7), ("ue", 0.3)], // ㅞ
+    &[("wi", 0.6), ("ui", 0.4)], // ㅟ
+    &[("u", 0.5), ("yu", 0.3), ("ew", 0.2)], // ㅠ
+    &[("", 0.7), ("e", 0.2), ("u", 0.1)], // ㅡ
+    &[("ui", 1.0)], // ㅢ
+    &[("i", 0.7), ("y", 0.2), ("ee", 0.1)], // ㅣ
+];
+
+const TAIL_SPELLINGS: [Options; 28] = [
+    &[("", 1.0)],
+    &[("k", 0.5), ("c", 0.3), ("ck", 0.2)], // ㄱ
+    &[("k", 1.0)], // ㄲ
+    &[("x", 0.6), ("ks", 0.4)], // ㄳ
+    &[("n", 1.0)], // ㄴ
+    &[("nj", 1.0)], // ㄵ
+    &[("n", 1.0)], // ㄶ
+    &[("t", 0.7), ("d", 0.3)], // ㄷ
+    &[("l", 0.7), ("ll", 0.3)], // ㄹ
+    &[("lk", 1.0)], // ㄺ
+    &[("lm", 1.0)], // ㄻ
+    &[("lb", 1.0)], // ㄼ
+    &[("ls", 1.0)], // ㄽ
+    &[("lt", 1.0)], // ㄾ
+    &[("lp", 1.0)], // ㄿ
+    &[("l", 1.0)], // ㅀ
+    &[("m", 1.0)], // ㅁ
+    &[("p", 0.6), ("b", 0.4)], // ㅂ
+    &[("ps", 1.0)], // ㅄ
+    &[("t", 0.5), ("s", 0.5)], // ㅅ
+    &[("t", 0.5), ("ss", 0.5)], // ㅆ
+    &[("ng", 0.7), ("n", 0.3)], // ㅇ
+    &[("t", 0.6), ("ge", 0.4)], // ㅈ
+    &[("t", 0.6), ("ch", 0.4)], // ㅊ
+    &[("k", 1.0)], // ㅋ
+    &[("t", 1.0)], // ㅌ
+    &[("p", 1.0)], // ㅍ
+    &[("", 1.0)], // ㅎ
+];
+
+// Open final syllables are where loanwords lose most of their spelling:
+// 컴퓨터 ends in "-er", 케이크 in a silent "e", 해피 in "-y".
+const FINAL_EO: Options = &[("er", 0.6), ("o", 0.2), ("a", 0.2)];
+const FINAL_EU: Options = &[("e", 0.5), ("", 0.5)];
+const FINAL_I: Options = &[("y", 0.4), ("i", 0.5), ("ee", 0.1)];
+
+/// A possible original spelling of a loanword.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    pub spelling: String,
+    pub score: f64,
+}
+
+/// Returns up to `n` candidate Latin spellings for a Hangul loanword, best first.
+///
+/// The plain romanization is always included as the last resort candidate.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::loanword::latin_candidates;
+/// let candidates = latin_candidates("컴퓨터", 5);
+/// assert_eq!("computer", candidates[0].spelling);
+/// assert_eq!("keompyuteo", candidates[candidates.len() - 1].spelling);
+/// ```
+pub fn latin_candidates(word: &str, n: usize) -> Vec<Candidate> {
+    if n == 0 {
+        return vec![];
+    }
+    let letters = word.chars().map(Letter::new).collect::<Vec<Letter>>();
+    let mut beam = vec![(String::new(), 1.0)];
+    for (i, letter) in letters.iter().enumerate() {
+        match letter {
+            Letter::HangulLetter(h) => {
+                let is_final = !letters.get(i + 1).is_some_and(|l| l.is_hangul());
+                for options in syllable_options(h, is_final) {
+                    beam = extended(beam, options);
+                }
+            }
+            Letter::OtherLetter(c) => {
+                for (spelling, _) in beam.iter_mut() {
+                    spelling.push(*c);
+                }
+            }
+        }
+    }
+    let literal = KoreanSentence::new(word).roman();
+    let mut candidates = beam.into_iter()
+        .filter(|(spelling, _)| *spelling != literal)
+        .take(n - 1)
+        .map(|(spelling, score)| Candidate { spelling, score })
+        .collect::<Vec<Candidate>>();
+    candidates.push(Candidate { spelling: literal, score: 0.0 });
+    candidates
+}
+
+fn syllable_options(h: &Hangul, is_final: bool) -> [Options; 3] {
+    let vowel = match (is_final && h.tail().index() == 0, h.vowel().index()) {
+        (true, 4) => FINAL_EO,
+        (true, 18) => FINAL_EU,
+        (true, 20) => FINAL_I,
+        (_, v) => VOWEL_SPELLINGS[v],
+    };
+    [LEAD_SPELLINGS[h.lead().index()], vowel, TAIL_SPELLINGS[h.tail().index()]]
+}
+
+fn extended(beam: Vec<(String, f64)>, options: Options) -> Vec<(String, f64)> {
+    let mut merged: HashMap<String, f64> = HashMap::new();
+    for (spelling, score) in beam {
+        for (suffix, weight) in options {
+            *merged.entry(format!("{}{}", spelling, suffix)).or_insert(0.0) += score * weight;
+        }
+    }
+    let mut next = merged.into_iter().collect::<Vec<(String, f64)>>();
+    next.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    next.truncate(BEAM_WIDTH);
+    next
+}
@@ -0,0 +1,113 @@
+/// Romanizes a string literal letter by letter at compile time, as
+/// [`KoreanSentence::roman`](crate::hangul::KoreanSentence::roman) does
+/// without rules.
+///
+/// # Examples
+///
+/// ```
+/// const GREETING: &str = jamo::roman!("안녕하세요");
+/// assert_eq!("annyeonghaseyo", GREETING);
+/// ```
+#[macro_export]
+macro_rules! roman {
+    ($s:expr) => {
+        $crate::__render_const!($s, true)
+    };
+}
+
+/// Decomposes the syllables of a string literal into conjoining jamo at
+/// compile time, as [`KoreanSentence::hangul_string`](crate::hangul::KoreanSentence::hangul_string) does.
+///
+/// # Examples
+///
+/// ```
+/// const HAN: &str = jamo::jamo!("한");
+/// assert_eq!("\u{1112}\u{1161}\u{11ab}", HAN);
+/// ```
+#[macro_export]
+macro_rules! jamo {
+    ($s:expr) => {
+        $crate::__render_const!($s, false)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __render_const {
+    ($s:expr, $roman:expr) => {{
+        const LEN: usize = $crate::hangul::render_const::<0>($s, $roman).1;
+        const BYTES: [u8; LEN] = $crate::hangul::render_const::<LEN>($s, $roman).0;
+        const STR: &str = match ::core::str::from_utf8(&BYTES) {
+            Ok(s) => s,
+            Err(_) => "",
+        };
+        STR
+    }};
+}
+
+/// Builds an array of [`Rule`](crate::rules::Rule)s from `tail + lead =>
+/// new_tail + new_lead` entries, checking at compile time that the patterns
+/// name real jamo. An output of `_` keeps the jamo, and `tail` or `lead` puts
+/// the old tail or lead there. The array can initialize a `static`.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// use jamo::rules::Rule;
+/// static MY_RULES: [Rule; 3] = jamo::rules! {
+///     "h" + "" => "" + "";
+///     "*" + "" => "" + tail;
+///     "g" + "n" => "ng" + _;
+/// };
+/// assert_eq!("h+", MY_RULES[0].name);
+/// assert_eq!("hangnyeon joa", KoreanSentence::new("학년 좋아").with_rules(&MY_RULES[..]).applied().roman());
+/// ```
+///
+/// Patterns and outputs that are not jamo fail to compile:
+///
+/// ```compile_fail
+/// static BAD: [jamo::rules::Rule; 1] = jamo::rules! { "x" + "n" => "ng" + _; };
+/// ```
+///
+/// ```compile_fail
+/// static BAD: [jamo::rules::Rule; 1] = jamo::rules! { "g" + "n" => "nq" + _; };
+/// ```
+#[macro_export]
+macro_rules! rules {
+    ($($tail:literal + $lead:literal => $new_tail:tt + $new_lead:tt;)*) => {
+        [$({
+            const _: () = assert!($crate::hangul::is_tail_pattern($tail), "not a tail pattern");
+            const _: () = assert!($crate::hangul::is_lead_pattern($lead), "not a lead pattern");
+            $crate::rules::Rule {
+                name: ::std::borrow::Cow::Borrowed(concat!($tail, "+", $lead)),
+                tail: ::std::borrow::Cow::Borrowed($tail),
+                lead: ::std::borrow::Cow::Borrowed($lead),
+                new_tail: $crate::__rule_output!(new_tail, $new_tail),
+                new_lead: $crate::__rule_output!(new_lead, $new_lead),
+            }
+        }),*]
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rule_output {
+    ($position:ident, _) => {
+        $crate::rules::RuleOutput::Keep
+    };
+    ($position:ident, tail) => {
+        $crate::rules::RuleOutput::Tail
+    };
+    ($position:ident, lead) => {
+        $crate::rules::RuleOutput::Lead
+    };
+    (new_tail, $s:literal) => {{
+        const _: () = assert!($crate::hangul::is_tail_pattern($s), "not a tail");
+        $crate::rules::RuleOutput::Set(::std::borrow::Cow::Borrowed($s))
+    }};
+    (new_lead, $s:literal) => {{
+        const _: () = assert!($crate::hangul::is_lead_pattern($s), "not a lead");
+        $crate::rules::RuleOutput::Set(::std::borrow::Cow::Borrowed($s))
+    }};
+}
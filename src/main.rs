@@ -1,19 +1,112 @@
 extern crate jamo;
 
+mod cli;
+
+use std::io::{self, Write};
+use std::process;
+
 use jamo::hangul::{KoreanSentence};
+use jamo::rules::RuleProfile;
+
+use cli::{Format, RuleChoice};
 
 
 fn main() {
+    // Arguments that are not valid UTF-8 are converted lossily rather than
+    // panicking, as `std::env::args` would.
+    let args = std::env::args_os().skip(1).map(|arg| arg.into_string().unwrap_or_else(|arg| {
+        eprintln!("jamo: argument {:?} is not valid UTF-8", arg);
+        arg.to_string_lossy().into_owned()
+    })).collect::<Vec<String>>();
+    match run(args) {
+        Ok(()) => {}
+        // The reader went away, as with `jamo ... | head`; there is no one to tell.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(e) => {
+            eprintln!("jamo: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn run(mut args: Vec<String>) -> io::Result<()> {
+    let rules = match args.iter().position(|a| a == "--rules") {
+        Some(i) if i + 1 < args.len() => {
+            let value = args.drain(i..i + 2).nth(1).unwrap();
+            cli::rules_arg(&value)?
+        }
+        Some(_) => return Err(io::Error::new(io::ErrorKind::InvalidInput, "--rules needs a value")),
+        None => RuleChoice::Profile(RuleProfile::Standard),
+    };
+    let format = match args.iter().position(|a| a == "--format") {
+        Some(i) => match args.drain(i..(i + 2).min(args.len())).nth(1).as_deref() {
+            Some("table") => Format::Table,
+            Some("blocks") => Format::Blocks,
+            Some("json") => Format::Json,
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "--format needs blocks, table or json")),
+        },
+        None => Format::Blocks,
+    };
+    let strict = match args.iter().position(|a| a == "--strict-utf8") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+    match args.iter().position(|a| a == "--watch-rules") {
+        Some(i) if i + 1 < args.len() => {
+            let path = args.drain(i..i + 2).nth(1).unwrap();
+            match args.first().map(String::as_str) {
+                None | Some("repl") => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput, "--watch-rules needs convert or text")),
+                _ => cli::watch::run(&path, |rules| dispatch(&args, rules, format, strict)),
+            }
+        }
+        Some(_) => Err(io::Error::new(io::ErrorKind::InvalidInput, "--watch-rules needs a file")),
+        None => dispatch(&args, rules, format, strict),
+    }
+}
+
+fn dispatch(args: &[String], rules: RuleChoice, format: Format, strict: bool) -> io::Result<()> {
+    match args.first().map(String::as_str) {
+        None => demo(),
+        Some("repl") => cli::repl::run(io::stdin().lock(), cli::console::stdout(), rules, strict),
+        Some("convert") => cli::convert::run(&args[1..], rules, format, strict),
+        Some("filter") => cli::filter::run(&args[1..], rules, format, strict),
+        #[cfg(feature = "serve")]
+        Some("serve") => cli::serve::run(&args[1..], rules),
+        Some(arg) if arg.starts_with('-') => {
+            eprintln!("{}", cli::USAGE);
+            process::exit(2);
+        }
+        Some(_) => {
+            let sentence = rules.apply_to(KoreanSentence::new(&args.join(" ")));
+            match format {
+                Format::Table => write!(cli::console::stdout(), "{}", sentence.format_table()),
+                Format::Json => writeln!(cli::console::stdout(), "{}", rules.to_json(&args.join(" "))),
+                Format::Blocks => {
+                    display(&sentence)?;
+                    display(&sentence.applied())
+                }
+            }
+        }
+    }
+}
+
+fn demo() -> io::Result<()> {
     let sentence = KoreanSentence::new("원하시는 페이지를 찾을 수가 없습니다. 좋아요.");
-    display(&sentence);
+    display(&sentence)?;
     let sentence = sentence.applied();
-    display(&sentence);
+    display(&sentence)
 }
 
-fn display(s: &KoreanSentence) {
-    println!("[Roman]\n{}\n[Jamo]\n{}\n[Hangul]\n{}",
+// Output goes through `writeln!` rather than `println!` so a closed pipe or
+// console ends the run with an error instead of a panic.
+fn display(s: &KoreanSentence) -> io::Result<()> {
+    writeln!(cli::console::stdout(), "[Roman]\n{}\n[Jamo]\n{}\n[Hangul]\n{}",
              s.roman(),
              s.jamo(),
              s.hangul_string(),
-    );
+    )
 }
@@ -0,0 +1,202 @@
+//! Numbers in Korean words: the Sino-Korean reading (일, 이, 삼), the native
+//! reading (하나, 둘, 셋) and counting with counters (분류사).
+
+const DIGITS: &str = "영일이삼사오육칠팔구";
+pub(crate) const SMALL_UNITS: [(char, u64); 3] = [('십', 10), ('백', 100), ('천', 1000)];
+pub(crate) const LARGE_UNITS: [(char, u64); 4] = [
+    ('만', 10_000), ('억', 100_000_000), ('조', 1_000_000_000_000), ('경', 10_000_000_000_000_000), ];
+
+const NATIVE_ONES: [&str; 10] = ["", "하나", "둘", "셋", "넷", "다섯", "여섯", "일곱", "여덟", "아홉"];
+const NATIVE_TENS: [&str; 10] = ["", "열", "스물", "서른", "마흔", "쉰", "예순", "일흔", "여든", "아흔"];
+// The forms of 하나 to 넷 before a counter; 스물 likewise becomes 스무.
+const ATTRIBUTIVE_ONES: [&str; 5] = ["", "한", "두", "세", "네"];
+
+// Counters counted with native numbers. Any other counter takes Sino-Korean ones.
+const NATIVE_COUNTERS: [&str; 25] = [
+    "명", "사람", "개", "마리", "살", "시", "시간", "번", "권", "잔", "병", "장", "대", "채", "벌",
+    "켤레", "그루", "송이", "달", "가지", "군데", "곳", "줄", "통", "쌍", ];
+
+/// Which set of numbers a count is read with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumeralSystem {
+    /// 하나, 둘, 셋: for counting people, things and hours, up to 99.
+    Native,
+    /// 일, 이, 삼: for money, dates, minutes and measurements.
+    Sino,
+}
+
+/// Returns `n` read in Sino-Korean numbers, spaced every four digits as
+/// Korean writes them. A leading 일 is dropped before 십, 백, 천 and 만.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::numeral::sino;
+/// assert_eq!("영", sino(0));
+/// assert_eq!("삼천오백", sino(3500));
+/// assert_eq!("만 십일", sino(10_011));
+/// assert_eq!("일억 이천만", sino(120_000_000));
+/// ```
+pub fn sino(n: u64) -> String {
+    if n == 0 {
+        return "영".to_string();
+    }
+    let mut groups = vec![];
+    let mut rest = n;
+    let mut scale = 0usize;
+    while rest > 0 {
+        let group = rest % 10_000;
+        if group > 0 {
+            let unit = scale.checked_sub(1).map(|i| LARGE_UNITS[i].0.to_string()).unwrap_or_default();
+            groups.push(match (group, scale) {
+                (1, 1) => unit,
+                _ => format!("{}{}", sino_group(group), unit),
+            });
+        }
+        rest /= 10_000;
+        scale += 1;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+fn sino_group(group: u64) -> String {
+    let digits = DIGITS.chars().collect::<Vec<char>>();
+    let places = [(group / 1000, Some('천')), (group / 100 % 10, Some('백')), (group / 10 % 10, Some('십')), (group % 10, None)];
+    let mut reading = String::new();
+    for (digit, unit) in places {
+        match (digit, unit) {
+            (0, _) => {}
+            (1, Some(unit)) => reading.push(unit),
+            (d, unit) => reading.extend(std::iter::once(digits[d as usize]).chain(unit)),
+        }
+    }
+    reading
+}
+
+/// Returns `n` read in native numbers, or `None` outside 1 to 99, which
+/// native numbers do not reach.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::numeral::native;
+/// assert_eq!(Some("스물셋".to_string()), native(23));
+/// assert_eq!(Some("열".to_string()), native(10));
+/// assert_eq!(None, native(100));
+/// ```
+pub fn native(n: u64) -> Option<String> {
+    match n {
+        1..=99 => Some(format!("{}{}", NATIVE_TENS[(n / 10) as usize], NATIVE_ONES[(n % 10) as usize])),
+        _ => None,
+    }
+}
+
+/// Returns the form of a native number taken before a counter: 한, 두, 세,
+/// 네 and 스무 stand for 하나, 둘, 셋, 넷 and 스물.
+fn native_attributive(n: u64) -> Option<String> {
+    match (n / 10, n % 10) {
+        (2, 0) => Some("스무".to_string()),
+        (tens, ones @ 1..=4) if tens < 10 => Some(format!("{}{}", NATIVE_TENS[tens as usize], ATTRIBUTIVE_ONES[ones as usize])),
+        _ => native(n),
+    }
+}
+
+/// Returns the set of numbers `counter` is counted with.
+pub fn counter_system(counter: &str) -> NumeralSystem {
+    match NATIVE_COUNTERS.contains(&counter) {
+        true => NumeralSystem::Native,
+        false => NumeralSystem::Sino,
+    }
+}
+
+/// Returns `n` of `counter` in words, read with the numbers the counter
+/// takes (see [`count_with`]).
+///
+/// # Examples
+///
+/// ```
+/// use jamo::numeral::count;
+/// assert_eq!("세 명", count(3, "명"));
+/// assert_eq!("스무 살", count(20, "살"));
+/// assert_eq!("백스물한 개", count(121, "개"));
+/// assert_eq!("삼천오백 원", count(3500, "원"));
+/// assert_eq!("유월", count(6, "월"));
+/// ```
+pub fn count(n: u64, counter: &str) -> String {
+    count_with(n, counter, counter_system(counter))
+}
+
+/// Returns `n` of `counter` in words, read with `system`. Native numbers
+/// stop at 99, so hundreds and above are read in Sino-Korean with the rest
+/// native (백스물한 명). Months are written solid, with 유월 and 시월 for 6
+/// and 10.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::numeral::{count_with, NumeralSystem};
+/// assert_eq!("삼 번", count_with(3, "번", NumeralSystem::Sino));
+/// assert_eq!("세 번", count_with(3, "번", NumeralSystem::Native));
+/// ```
+pub fn count_with(n: u64, counter: &str, system: NumeralSystem) -> String {
+    if counter == "월" && system == NumeralSystem::Sino {
+        return match n {
+            6 => "유월".to_string(),
+            10 => "시월".to_string(),
+            _ => format!("{}월", sino(n)),
+        };
+    }
+    let number = match (system, n % 100) {
+        (NumeralSystem::Sino, _) | (NumeralSystem::Native, 0) => sino(n),
+        (NumeralSystem::Native, rest) => {
+            let hundreds = if n >= 100 { sino(n - rest) } else { String::new() };
+            format!("{}{}", hundreds, native_attributive(rest).unwrap_or_default())
+        }
+    };
+    format!("{} {}", number, counter)
+}
+
+/// Parses a Sino-Korean number written in Hangul without spaces, such as
+/// 삼천오백 or 이십일, into its value.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::numeral::parse_sino;
+/// assert_eq!(Some(3500), parse_sino("삼천오백"));
+/// assert_eq!(Some(10_000), parse_sino("만"));
+/// assert_eq!(None, parse_sino("이삼"));
+/// assert_eq!(None, parse_sino("천천"));
+/// ```
+pub fn parse_sino(s: &str) -> Option<u64> {
+    let (mut total, mut section, mut digit) = (0u64, 0u64, None);
+    let (mut small_limit, mut large_limit) = (u64::MAX, u64::MAX);
+    for c in s.chars() {
+        if let Some(d) = DIGITS.chars().position(|d| d == c) {
+            if digit.is_some() {
+                return None;
+            }
+            digit = Some(d as u64);
+        } else if let Some(&(_, unit)) = SMALL_UNITS.iter().find(|&&(u, _)| u == c) {
+            if unit >= small_limit {
+                return None;
+            }
+            section += digit.take().unwrap_or(1) * unit;
+            small_limit = unit;
+        } else if let Some(&(_, unit)) = LARGE_UNITS.iter().find(|&&(u, _)| u == c) {
+            if unit >= large_limit {
+                return None;
+            }
+            let value = section + digit.take().unwrap_or(0);
+            total = total.checked_add(value.max(1).checked_mul(unit)?)?;
+            (section, small_limit, large_limit) = (0, u64::MAX, unit);
+        } else {
+            return None;
+        }
+    }
+    match s.is_empty() {
+        true => None,
+        false => total.checked_add(section + digit.unwrap_or(0)),
+    }
+}
@@ -0,0 +1,790 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+use crate::blocks::{self, Block, Position};
+use crate::hangul::{Hangul, Jamo};
+
+/// A constraint on the lead, vowel or tail of one syllable, compared as
+/// compatibility jamo.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Slot {
+    Any,
+    Jamo(char),
+    OneOf(Vec<char>),
+    NoneOf(Vec<char>),
+    /// No tail. Leads and vowels never match it.
+    Empty,
+}
+
+impl Slot {
+    /// Returns whether the jamo of the slot can stand in `position`.
+    fn fits(&self, position: Position) -> bool {
+        match self {
+            Slot::Jamo(c) => blocks::index(*c, position).is_some(),
+            _ => true,
+        }
+    }
+
+    fn matches(&self, jamo: Option<char>) -> bool {
+        match (self, jamo) {
+            (Slot::Any, _) => true,
+            (Slot::Empty, jamo) => jamo.is_none(),
+            (Slot::Jamo(c), Some(j)) => *c == j,
+            (Slot::OneOf(set), Some(j)) => set.contains(&j),
+            (Slot::NoneOf(set), jamo) => !jamo.is_some_and(|j| set.contains(&j)),
+            (_, None) => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SyllableSlots {
+    lead: Slot,
+    vowel: Slot,
+    tail: Slot,
+}
+
+/// A word of a fixed number of syllables with constraints on their jamo, for
+/// word games: Wordle-style guessing, crossword fill and the like.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::pattern::{Slot, WordPattern};
+/// let words = ["가방", "가족", "고양이", "기린", "나방"];
+/// // Two syllables, the first led by ㄱ, the second with the vowel ㅏ.
+/// let pattern = WordPattern::new(2).lead(0, Slot::Jamo('ㄱ')).vowel(1, Slot::Jamo('ㅏ'));
+/// assert_eq!(vec!["가방"], pattern.filter(words.iter().copied()));
+/// // As in Wordle: ㅇ is somewhere in the word, ㅣ nowhere.
+/// let pattern = WordPattern::new(2).contains('ㅇ').excludes('ㅣ');
+/// assert_eq!(vec!["가방", "나방"], pattern.filter(words.iter().copied()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordPattern {
+    syllables: Vec<SyllableSlots>,
+    required: Vec<char>,
+    excluded: Vec<char>,
+}
+
+impl WordPattern {
+    /// Returns a pattern matching any word of `len` Hangul syllables.
+    pub fn new(len: usize) -> Self {
+        let any = SyllableSlots { lead: Slot::Any, vowel: Slot::Any, tail: Slot::Any };
+        WordPattern { syllables: vec![any; len], required: vec![], excluded: vec![] }
+    }
+
+    /// Parses a pattern of whitespace-separated syllables, each either a
+    /// syllable to match exactly or a lead, a vowel and an optional tail,
+    /// where `?` matches any jamo and `_` no tail. A left-out tail matches
+    /// any tail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::pattern::WordPattern;
+    /// let pattern = WordPattern::parse("ㄱ?_ 방").unwrap();
+    /// assert!(pattern.matches("가방"));
+    /// assert!(!pattern.matches("각방"));
+    /// assert!(WordPattern::parse("ㅏㄱ").is_none());
+    /// ```
+    pub fn parse(s: &str) -> Option<Self> {
+        let specs = s.split_whitespace().collect::<Vec<&str>>();
+        let mut pattern = Self::new(specs.len());
+        for (i, spec) in specs.into_iter().enumerate() {
+            let chars = spec.chars().collect::<Vec<char>>();
+            let slot = |c: char, tail: bool| match c {
+                '?' => Slot::Any,
+                '_' if tail => Slot::Empty,
+                c => Slot::Jamo(c),
+            };
+            pattern.syllables[i] = match chars[..] {
+                [c] => {
+                    let h = Hangul::try_from(c).ok()?;
+                    let tail = h.tail().compat_char().map_or(Slot::Empty, Slot::Jamo);
+                    let (lead, vowel) = (h.lead().compat_char()?, h.vowel().compat_char()?);
+                    SyllableSlots { lead: Slot::Jamo(lead), vowel: Slot::Jamo(vowel), tail }
+                }
+                [lead, vowel] => SyllableSlots { lead: slot(lead, false), vowel: slot(vowel, false), tail: Slot::Any },
+                [lead, vowel, tail] => SyllableSlots { lead: slot(lead, false), vowel: slot(vowel, false), tail: slot(tail, true) },
+                _ => return None,
+            };
+            let slots = &pattern.syllables[i];
+            if !slots.lead.fits(Position::Lead) || !slots.vowel.fits(Position::Vowel) || !slots.tail.fits(Position::Tail) {
+                return None;
+            }
+        }
+        Some(pattern)
+    }
+
+    /// Constrains the lead of syllable `index`. An index past the end is ignored.
+    pub fn lead(mut self, index: usize, slot: Slot) -> Self {
+        if let Some(s) = self.syllables.get_mut(index) {
+            s.lead = slot;
+        }
+        self
+    }
+
+    /// Constrains the vowel of syllable `index`. An index past the end is ignored.
+    pub fn vowel(mut self, index: usize, slot: Slot) -> Self {
+        if let Some(s) = self.syllables.get_mut(index) {
+            s.vowel = slot;
+        }
+        self
+    }
+
+    /// Constrains the tail of syllable `index`. An index past the end is ignored.
+    pub fn tail(mut self, index: usize, slot: Slot) -> Self {
+        if let Some(s) = self.syllables.get_mut(index) {
+            s.tail = slot;
+        }
+        self
+    }
+
+    /// Requires `jamo` as a lead, vowel or tail somewhere in the word.
+    pub fn contains(mut self, jamo: char) -> Self {
+        self.required.push(jamo);
+        self
+    }
+
+    /// Rules out `jamo` as any lead, vowel or tail of the word.
+    pub fn excludes(mut self, jamo: char) -> Self {
+        self.excluded.push(jamo);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.syllables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.syllables.is_empty()
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        let syllables = match word.chars().map(Hangul::try_from).collect::<Result<Vec<Hangul>, _>>() {
+            Ok(syllables) if syllables.len() == self.syllables.len() => syllables,
+            _ => return false,
+        };
+        let mut jamo = vec![];
+        for (h, slots) in syllables.iter().zip(&self.syllables) {
+            let (lead, vowel, tail) = (h.lead().compat_char(), h.vowel().compat_char(), h.tail().compat_char());
+            if !slots.lead.matches(lead) || !slots.vowel.matches(vowel) || !slots.tail.matches(tail) {
+                return false;
+            }
+            jamo.extend([lead, vowel, tail].iter().flatten());
+        }
+        self.required.iter().all(|c| jamo.contains(c)) && !self.excluded.iter().any(|c| jamo.contains(c))
+    }
+
+    /// Returns the words of `words` the pattern matches, in order.
+    pub fn filter<'a, I: IntoIterator<Item = &'a str>>(&self, words: I) -> Vec<&'a str> {
+        words.into_iter().filter(|w| self.matches(w)).collect()
+    }
+}
+
+/// An error in a [`JamoRegex`] pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternError {
+    /// One-based column of the error, in chars.
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl Error for PatternError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Class {
+    Any,
+    Consonant,
+    Vowel,
+    Tense,
+    Aspirated,
+    Nasal,
+    Liquid,
+    Obstruent,
+    Space,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Node {
+    Literal(char),
+    Class(Class),
+    Set(Vec<char>, bool),
+    SyllableStart,
+    SyllableEnd,
+    Group(Vec<Vec<Piece>>),
+}
+
+/// An instruction of a compiled [`JamoRegex`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Inst {
+    /// Consumes a token matching a literal, set or class node.
+    Consume(Node),
+    SyllableStart,
+    SyllableEnd,
+    /// Continues at both targets, preferring the first.
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Piece {
+    node: Node,
+    min: usize,
+    max: Option<usize>,
+}
+
+/// One jamo of decomposed text, or a character that is not Hangul.
+struct Token {
+    c: char,
+    jamo: Option<Jamo>,
+    /// The index of the char the token comes from.
+    source: usize,
+    starts_syllable: bool,
+    ends_syllable: bool,
+}
+
+/// A regular expression over decomposed text, where syllables are split into
+/// their lead, vowel and tail and each is matched separately.
+///
+/// Besides literal jamo and characters, `.` matches any jamo or character,
+/// `[ㄱㄴ]` and `[^ㄱㄴ]` sets of them, and these classes any jamo of a kind:
+///
+/// | class | matches |
+/// | --- | --- |
+/// | `\C` | consonants |
+/// | `\V` | vowels |
+/// | `\T` | tense consonants: ㄲ, ㄸ, ㅃ, ㅆ, ㅉ |
+/// | `\A` | aspirated consonants: ㅋ, ㅌ, ㅍ, ㅊ |
+/// | `\N` | nasals: ㄴ, ㅁ and ㅇ as a tail |
+/// | `\L` | ㄹ |
+/// | `\O` | obstruents |
+/// | `\s` | whitespace |
+///
+/// A compatibility jamo outside a syllable counts as a lead where it can be
+/// one, so a lone ㅇ is not a nasal. Conjoining jamo in literals and sets
+/// match their compatibility forms. A syllable in a literal matches exactly
+/// that whole syllable, as if written `\<` lead vowel tail `\>`; syllables
+/// in sets are an error.
+///
+/// `\<` and `\>` anchor at the start and end of a syllable, so `\<\T` is a
+/// tense lead and `ㄱ\>` a ㄱ tail. Groups `(...)`, alternation `|` and the
+/// greedy quantifiers `*`, `+` and `?` work as in other regular expressions.
+/// Matching runs in time linear in the text for a given pattern, without
+/// backtracking, so nested quantifiers and long inputs are safe.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::pattern::JamoRegex;
+/// // Syllables with a tense lead.
+/// let regex = JamoRegex::new(r"\<\T").unwrap();
+/// let found = regex.find_iter("아빠가 떡을 샀다").map(|m| m.as_str()).collect::<Vec<&str>>();
+/// assert_eq!(vec!["빠", "떡"], found);
+/// // A ㄱ tail before a syllable led by a nasal.
+/// assert!(JamoRegex::new(r"ㄱ\>\<\N").unwrap().is_match("국물"));
+/// assert!(!JamoRegex::new(r"ㄱ\>\<\N").unwrap().is_match("국어"));
+/// assert!(JamoRegex::new("[\u{1100}ㄴ]ㅏ").unwrap().is_match("가"));
+/// assert!(JamoRegex::new("가방").unwrap().is_match("가방"));
+/// assert!(!JamoRegex::new("가").unwrap().is_match("각"));
+/// assert!(JamoRegex::new("[가나]").is_err());
+///
+/// let long = "가".repeat(20_000);
+/// assert_eq!(long.len(), JamoRegex::new(".*").unwrap().find_iter(&long).next().unwrap().range().end);
+/// assert!(!JamoRegex::new("(ㄱ*)*ㄴ").unwrap().is_match(&"ㄱ".repeat(5_000)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JamoRegex {
+    program: Vec<Inst>,
+}
+
+/// A match of a [`JamoRegex`], spanning the whole chars it touches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JamoMatch<'a> {
+    text: &'a str,
+    range: (usize, usize),
+}
+
+impl<'a> JamoMatch<'a> {
+    /// Returns the byte range of the chars the match touches.
+    pub fn range(&self) -> Range<usize> {
+        self.range.0..self.range.1
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.range()]
+    }
+}
+
+impl JamoRegex {
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        let chars = pattern.chars().collect::<Vec<char>>();
+        let mut i = 0;
+        let alternatives = parse_alternatives(&chars, &mut i)?;
+        match chars.get(i) {
+            Some(_) => Err(PatternError { column: i + 1, message: "unmatched )".to_string() }),
+            None => {
+                let mut program = vec![];
+                compile_alternatives(&alternatives, &mut program);
+                program.push(Inst::Match);
+                Ok(JamoRegex { program })
+            }
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find_iter(text).next().is_some()
+    }
+
+    /// Returns the leftmost non-overlapping matches in `text`. Empty matches
+    /// are skipped.
+    pub fn find_iter<'a>(&self, text: &'a str) -> impl Iterator<Item = JamoMatch<'a>> {
+        let bounds = text.char_indices().map(|(i, c)| (i, i + c.len_utf8())).collect::<Vec<(usize, usize)>>();
+        self.token_matches(&tokens(text)).into_iter()
+            .map(move |(_, first, last)| JamoMatch { text, range: (bounds[first].0, bounds[last].1) })
+    }
+
+    /// Returns `text` with every match replaced by the jamo and characters of
+    /// `replacement`. The syllables a match touches are recomposed from what
+    /// is left of them, so jamo can be swapped, removed or moved between
+    /// syllables; jamo that cannot be composed are left as compatibility jamo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::pattern::JamoRegex;
+    /// // A ㅆ lead, but not a ㅆ tail.
+    /// assert_eq!("살을 샀다", JamoRegex::new(r"\<ㅆ").unwrap().replace_all("쌀을 샀다", "ㅅ"));
+    /// // Swap ㅐ for ㅔ wherever it is.
+    /// assert_eq!("게가 세", JamoRegex::new("ㅐ").unwrap().replace_all("개가 새", "ㅔ"));
+    /// // Move a tail over a silent ㅇ, as liaison does.
+    /// assert_eq!("머거요", JamoRegex::new(r"ㄱ\>\<ㅇ").unwrap().replace_all("먹어요", "ㄱ"));
+    /// ```
+    pub fn replace_all(&self, text: &str, replacement: &str) -> String {
+        let chars = text.chars().collect::<Vec<char>>();
+        let tokens = tokens(text);
+        let mut replaced = String::with_capacity(text.len());
+        let mut next_char = 0;
+        let mut matches = self.token_matches(&tokens).into_iter().peekable();
+        while let Some((mut span, first, mut last)) = matches.next() {
+            replaced.extend(&chars[next_char..first]);
+            let region_start = tokens.iter().take_while(|t| t.source < first).count();
+            let mut region = tokens[region_start..span.start].iter().map(|t| t.c).collect::<Vec<char>>();
+            region.extend(replacement.chars());
+            // Matches touching the same chars are recomposed together.
+            while let Some((next, _, next_last)) = matches.next_if(|m| m.1 <= last) {
+                region.extend(tokens[span.end..next.start].iter().map(|t| t.c));
+                region.extend(replacement.chars());
+                span = next;
+                last = last.max(next_last);
+            }
+            let region_end = tokens.iter().take_while(|t| t.source <= last).count();
+            region.extend(tokens[span.end..region_end].iter().map(|t| t.c));
+            replaced.push_str(&recompose(&region));
+            next_char = last + 1;
+        }
+        replaced.extend(&chars[next_char..]);
+        replaced
+    }
+
+    /// Returns the token range of each match with the first and last char it touches.
+    fn token_matches(&self, tokens: &[Token]) -> Vec<(Range<usize>, usize, usize)> {
+        let mut matches = vec![];
+        let mut start = 0;
+        while let Some(span) = self.leftmost_match(tokens, start) {
+            matches.push((span.clone(), tokens[span.start].source, tokens[span.end - 1].source));
+            start = span.end;
+        }
+        matches
+    }
+
+    /// Runs the program over `tokens` from `from` as a Pike VM, keeping one
+    /// thread per instruction in priority order, and returns the leftmost
+    /// non-empty match the backtracking order would have preferred. Time is
+    /// linear in the tokens scanned times the program length.
+    fn leftmost_match(&self, tokens: &[Token], from: usize) -> Option<Range<usize>> {
+        let mut threads = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        let mut found = None;
+        for at in from..=tokens.len() {
+            if found.is_none() {
+                self.add_thread(&mut threads, tokens, at, 0, at);
+            }
+            if threads.list.is_empty() && found.is_some() {
+                break;
+            }
+            next.clear();
+            for &(pc, start) in &threads.list {
+                let consumed = match &self.program[pc] {
+                    Inst::Match if at > start => {
+                        found = Some(start..at);
+                        // Lower-priority threads can only find worse matches.
+                        break;
+                    }
+                    Inst::Consume(node) => tokens.get(at).is_some_and(|t| node.consumes(t)),
+                    _ => false,
+                };
+                if consumed {
+                    self.add_thread(&mut next, tokens, at + 1, pc + 1, start);
+                }
+            }
+            std::mem::swap(&mut threads, &mut next);
+        }
+        found
+    }
+
+    /// Adds the thread at `pc`, following jumps, splits and anchors with an
+    /// explicit stack so that the first branch of a split keeps priority.
+    fn add_thread(&self, threads: &mut Threads, tokens: &[Token], at: usize, pc: usize, start: usize) {
+        let mut stack = vec![pc];
+        while let Some(pc) = stack.pop() {
+            if !threads.visit(pc) {
+                continue;
+            }
+            match self.program[pc] {
+                Inst::Jump(to) => stack.push(to),
+                Inst::Split(first, second) => {
+                    stack.push(second);
+                    stack.push(first);
+                }
+                Inst::SyllableStart => {
+                    if tokens.get(at).is_some_and(|t| t.starts_syllable) {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::SyllableEnd => {
+                    if at > 0 && tokens[at - 1].ends_syllable {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::Consume(_) | Inst::Match => threads.list.push((pc, start)),
+            }
+        }
+    }
+}
+
+/// The threads of a [`JamoRegex`] run at one token, in priority order.
+struct Threads {
+    list: Vec<(usize, usize)>,
+    visited: Vec<bool>,
+}
+
+impl Threads {
+    fn new(len: usize) -> Self {
+        Threads { list: vec![], visited: vec![false; len] }
+    }
+
+    fn clear(&mut self) {
+        self.list.clear();
+        self.visited.iter_mut().for_each(|v| *v = false);
+    }
+
+    /// Marks `pc` visited, returning whether it was not already.
+    fn visit(&mut self, pc: usize) -> bool {
+        !std::mem::replace(&mut self.visited[pc], true)
+    }
+}
+
+impl Node {
+    fn consumes(&self, token: &Token) -> bool {
+        match self {
+            Node::Literal(c) => token.c == *c,
+            Node::Set(set, negated) => set.contains(&token.c) != *negated,
+            Node::Class(class) => class.matches(token),
+            Node::SyllableStart | Node::SyllableEnd | Node::Group(_) => false,
+        }
+    }
+}
+
+/// Compiles parsed alternatives into `program`, ending in the instruction
+/// after them.
+fn compile_alternatives(alternatives: &[Vec<Piece>], program: &mut Vec<Inst>) {
+    let mut jumps = vec![];
+    for (i, pieces) in alternatives.iter().enumerate() {
+        let split = (i + 1 < alternatives.len()).then(|| {
+            program.push(Inst::Split(program.len() + 1, 0));
+            program.len() - 1
+        });
+        for piece in pieces {
+            compile_piece(piece, program);
+        }
+        if let Some(split) = split {
+            jumps.push(program.len());
+            program.push(Inst::Jump(0));
+            program[split] = Inst::Split(split + 1, program.len());
+        }
+    }
+    let end = program.len();
+    for jump in jumps {
+        program[jump] = Inst::Jump(end);
+    }
+}
+
+fn compile_piece(piece: &Piece, program: &mut Vec<Inst>) {
+    let compile_node = |program: &mut Vec<Inst>| match &piece.node {
+        Node::Group(alternatives) => compile_alternatives(alternatives, program),
+        Node::SyllableStart => program.push(Inst::SyllableStart),
+        Node::SyllableEnd => program.push(Inst::SyllableEnd),
+        node => program.push(Inst::Consume(node.clone())),
+    };
+    match (piece.min, piece.max) {
+        (0, Some(1)) => {
+            let split = program.len();
+            program.push(Inst::Split(split + 1, 0));
+            compile_node(program);
+            program[split] = Inst::Split(split + 1, program.len());
+        }
+        (0, None) => {
+            let split = program.len();
+            program.push(Inst::Split(split + 1, 0));
+            compile_node(program);
+            program.push(Inst::Jump(split));
+            program[split] = Inst::Split(split + 1, program.len());
+        }
+        (1, None) => {
+            let body = program.len();
+            compile_node(program);
+            program.push(Inst::Split(body, program.len() + 1));
+        }
+        _ => compile_node(program),
+    }
+}
+
+impl Class {
+    fn matches(&self, token: &Token) -> bool {
+        let jamo = match (&token.jamo, self) {
+            (_, Class::Any) => return true,
+            (_, Class::Space) => return token.c.is_whitespace(),
+            (Some(jamo), _) => jamo,
+            (None, _) => return false,
+        };
+        match self {
+            Class::Consonant => !jamo.is_vowel(),
+            Class::Vowel => jamo.is_vowel(),
+            Class::Tense => jamo.is_tense(),
+            Class::Aspirated => jamo.is_aspirated(),
+            Class::Nasal => jamo.is_nasal(),
+            Class::Liquid => jamo.is_liquid(),
+            Class::Obstruent => jamo.is_obstruent(),
+            Class::Any | Class::Space => true,
+        }
+    }
+}
+
+fn parse_alternatives(chars: &[char], i: &mut usize) -> Result<Vec<Vec<Piece>>, PatternError> {
+    let mut alternatives: Vec<Vec<Piece>> = vec![vec![]];
+    while let Some(&c) = chars.get(*i) {
+        let column = *i + 1;
+        *i += 1;
+        let node = match c {
+            ')' => {
+                *i -= 1;
+                break;
+            }
+            '|' => {
+                alternatives.push(vec![]);
+                continue;
+            }
+            '*' | '+' | '?' => {
+                let piece = alternatives.last_mut().and_then(|a| a.last_mut())
+                    .filter(|p| (p.min, p.max) == (1, Some(1)))
+                    .ok_or_else(|| PatternError { column, message: format!("nothing to repeat with {}", c) })?;
+                (piece.min, piece.max) = match c {
+                    '*' => (0, None),
+                    '+' => (1, None),
+                    _ => (0, Some(1)),
+                };
+                continue;
+            }
+            '(' => {
+                let group = parse_alternatives(chars, i)?;
+                if chars.get(*i) != Some(&')') {
+                    return Err(PatternError { column, message: "unclosed (".to_string() });
+                }
+                *i += 1;
+                Node::Group(group)
+            }
+            '[' => {
+                let negated = chars.get(*i) == Some(&'^');
+                if negated {
+                    *i += 1;
+                }
+                let end = chars[*i..].iter().position(|&c| c == ']')
+                    .ok_or_else(|| PatternError { column, message: "unclosed [".to_string() })?;
+                if let Some(at) = chars[*i..*i + end].iter().position(|&c| Hangul::try_from(c).is_ok()) {
+                    return Err(PatternError { column: *i + at + 1, message: "syllable in a set".to_string() });
+                }
+                let set = chars[*i..*i + end].iter().map(|&c| blocks::to_compatibility(c).unwrap_or(c)).collect();
+                *i += end + 1;
+                Node::Set(set, negated)
+            }
+            '.' => Node::Class(Class::Any),
+            '\\' => {
+                let escaped = chars.get(*i).copied()
+                    .ok_or_else(|| PatternError { column, message: "trailing \\".to_string() })?;
+                *i += 1;
+                match escaped {
+                    'C' => Node::Class(Class::Consonant),
+                    'V' => Node::Class(Class::Vowel),
+                    'T' => Node::Class(Class::Tense),
+                    'A' => Node::Class(Class::Aspirated),
+                    'N' => Node::Class(Class::Nasal),
+                    'L' => Node::Class(Class::Liquid),
+                    'O' => Node::Class(Class::Obstruent),
+                    's' => Node::Class(Class::Space),
+                    '<' => Node::SyllableStart,
+                    '>' => Node::SyllableEnd,
+                    c if c.is_alphanumeric() => {
+                        return Err(PatternError { column, message: format!("unknown class \\{}", c) });
+                    }
+                    c => Node::Literal(c),
+                }
+            }
+            c => match Hangul::try_from(c) {
+                Ok(h) => syllable(&h),
+                Err(_) => Node::Literal(blocks::to_compatibility(c).unwrap_or(c)),
+            },
+        };
+        if let Some(pieces) = alternatives.last_mut() {
+            pieces.push(Piece { node, min: 1, max: Some(1) });
+        }
+    }
+    Ok(alternatives)
+}
+
+/// Returns a group matching exactly the syllable `h`.
+fn syllable(h: &Hangul) -> Node {
+    let mut nodes = vec![Node::SyllableStart];
+    nodes.extend([h.lead(), h.vowel(), h.tail()].iter().filter_map(|j| j.compat_char()).map(Node::Literal));
+    nodes.push(Node::SyllableEnd);
+    Node::Group(vec![nodes.into_iter().map(|node| Piece { node, min: 1, max: Some(1) }).collect()])
+}
+
+/// Splits `text` into tokens: syllables into their lead, vowel and tail as
+/// compatibility jamo, other chars whole.
+fn tokens(text: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    for (source, c) in text.chars().enumerate() {
+        match Hangul::try_from(c) {
+            Ok(h) => {
+                let parts = [h.lead(), h.vowel(), h.tail()].iter()
+                    .filter_map(|&j| j.compat_char().map(|c| (c, j.clone())))
+                    .collect::<Vec<(char, Jamo)>>();
+                let count = parts.len();
+                for (i, (c, jamo)) in parts.into_iter().enumerate() {
+                    tokens.push(Token { c, jamo: Some(jamo), source, starts_syllable: i == 0, ends_syllable: i + 1 == count });
+                }
+            }
+            Err(_) => {
+                let c = blocks::to_compatibility(c).unwrap_or(c);
+                let jamo = Jamo::try_from(c).ok().filter(|_| blocks::block(c) == Some(Block::Compatibility));
+                tokens.push(Token { c, jamo, source, starts_syllable: false, ends_syllable: false });
+            }
+        }
+    }
+    tokens
+}
+
+/// Composes compatibility jamo into syllables where a lead is followed by a
+/// vowel, taking a following consonant as the tail unless a vowel comes after it.
+fn recompose(jamo: &[char]) -> String {
+    let is = |i: usize, position| jamo.get(i).is_some_and(|&c| blocks::index(c, position).is_some());
+    let mut composed = String::new();
+    let mut i = 0;
+    while i < jamo.len() {
+        if is(i, Position::Lead) && is(i + 1, Position::Vowel) {
+            let tail = Some(i + 2).filter(|&t| is(t, Position::Tail) && !is(t + 1, Position::Vowel));
+            if let Ok(syllable) = blocks::compose(jamo[i], jamo[i + 1], tail.map(|t| jamo[t])) {
+                composed.push(syllable);
+                i += if tail.is_some() { 3 } else { 2 };
+                continue;
+            }
+        }
+        composed.push(jamo[i]);
+        i += 1;
+    }
+    composed
+}
+
+/// Returns whether the leads of `word` match the glob `pattern`, where `*`
+/// matches any run of leads and `?` a single one. Characters other than
+/// syllables stand for themselves. A lighter alternative to [`JamoRegex`]
+/// for filtering by 초성.
+///
+/// Syllables in `pattern` are reduced to their leads like those of `word`,
+/// so "가*" is the same pattern as "ㄱ*".
+///
+/// # Examples
+///
+/// ```
+/// use jamo::pattern::glob_match;
+/// assert!(glob_match("ㄱ*ㄴ", "가난"));
+/// assert!(glob_match("ㄱ*ㄴ", "기차역 안내"));
+/// assert!(glob_match("ㅅ?", "사과"));
+/// assert!(!glob_match("ㅅ?", "사과나무"));
+/// assert!(glob_match("기*ㅇ", "기차역"));
+/// ```
+pub fn glob_match(pattern: &str, word: &str) -> bool {
+    glob(&leads(pattern), &leads(word))
+}
+
+fn leads(text: &str) -> Vec<char> {
+    text.chars().map(|c| match Hangul::try_from(c) {
+        Ok(h) => h.lead().compat_char().unwrap_or(c),
+        Err(_) => blocks::to_compatibility(c).unwrap_or(c),
+    }).collect()
+}
+
+/// Returns whether the jamo of `word`, each syllable split into its lead,
+/// vowel and tail, match the glob `pattern`. Syllables in `pattern` are
+/// split the same way, so "강*" is the same pattern as "ㄱㅏㅇ*".
+///
+/// # Examples
+///
+/// ```
+/// use jamo::pattern::glob_match_jamo;
+/// assert!(glob_match_jamo("ㄱㅏ*", "강아지"));
+/// assert!(glob_match_jamo("*ㅇ?ㅈㅣ", "강아지"));
+/// assert!(!glob_match_jamo("ㄱㅏ", "강"));
+/// assert!(glob_match_jamo("강??ㅈ*", "강아지"));
+/// ```
+pub fn glob_match_jamo(pattern: &str, word: &str) -> bool {
+    let jamo = |text| tokens(text).iter().map(|t| t.c).collect::<Vec<char>>();
+    glob(&jamo(pattern), &jamo(word))
+}
+
+fn glob(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // The last star and the text position it was tried at.
+    let mut star = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    star = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,164 @@
+//! Phonological features of jamo, for feature-based comparisons and rule
+//! conditions.
+
+use crate::hangul::{Hangul, Jamo};
+
+/// Where a consonant is articulated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Place {
+    Bilabial,
+    Alveolar,
+    Palatal,
+    Velar,
+    Glottal,
+}
+
+/// How a consonant is articulated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Manner {
+    Stop,
+    Fricative,
+    Affricate,
+    Nasal,
+    Liquid,
+}
+
+/// The three-way laryngeal contrast of obstruents; nasals and ㄹ are voiced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Phonation {
+    Lax,
+    Tense,
+    Aspirated,
+    Voiced,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Height {
+    High,
+    Mid,
+    Low,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Backness {
+    Front,
+    Central,
+    Back,
+}
+
+/// The glide before the nucleus of a diphthong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Glide {
+    Y,
+    W,
+}
+
+/// The features of a jamo, as returned by
+/// [`Jamo::features`](crate::hangul::Jamo::features). A vowel's height,
+/// backness and rounding are those of its nucleus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Features {
+    Consonant { place: Place, manner: Manner, phonation: Phonation },
+    Vowel { height: Height, backness: Backness, rounded: bool, glide: Option<Glide> },
+}
+
+/// Returns the features of a single consonant given as a compatibility jamo.
+pub(crate) fn consonant(c: char) -> Option<Features> {
+    use self::Manner::*;
+    use self::Phonation::*;
+    use self::Place::*;
+    let (place, manner, phonation) = match c {
+        'ㄱ' => (Velar, Stop, Lax),
+        'ㄲ' => (Velar, Stop, Tense),
+        'ㅋ' => (Velar, Stop, Aspirated),
+        'ㄷ' => (Alveolar, Stop, Lax),
+        'ㄸ' => (Alveolar, Stop, Tense),
+        'ㅌ' => (Alveolar, Stop, Aspirated),
+        'ㅂ' => (Bilabial, Stop, Lax),
+        'ㅃ' => (Bilabial, Stop, Tense),
+        'ㅍ' => (Bilabial, Stop, Aspirated),
+        'ㅅ' => (Alveolar, Fricative, Lax),
+        'ㅆ' => (Alveolar, Fricative, Tense),
+        'ㅈ' => (Palatal, Affricate, Lax),
+        'ㅉ' => (Palatal, Affricate, Tense),
+        'ㅊ' => (Palatal, Affricate, Aspirated),
+        'ㅎ' => (Glottal, Fricative, Lax),
+        'ㄴ' => (Alveolar, Nasal, Voiced),
+        'ㅁ' => (Bilabial, Nasal, Voiced),
+        'ㅇ' => (Velar, Nasal, Voiced),
+        'ㄹ' => (Alveolar, Liquid, Voiced),
+        _ => return None,
+    };
+    Some(Features::Consonant { place, manner, phonation })
+}
+
+/// Returns the features of the vowel at `index` in the vowel table.
+pub(crate) fn vowel(index: usize) -> Option<Features> {
+    use self::Backness::*;
+    use self::Height::*;
+    let (height, backness, rounded, glide) = match index {
+        0 => (Low, Central, false, None),           // ㅏ
+        1 => (Low, Front, false, None),             // ㅐ
+        2 => (Low, Central, false, Some(Glide::Y)), // ㅑ
+        3 => (Low, Front, false, Some(Glide::Y)),   // ㅒ
+        4 => (Mid, Back, false, None),              // ㅓ
+        5 => (Mid, Front, false, None),             // ㅔ
+        6 => (Mid, Back, false, Some(Glide::Y)),    // ㅕ
+        7 => (Mid, Front, false, Some(Glide::Y)),   // ㅖ
+        8 => (Mid, Back, true, None),               // ㅗ
+        9 => (Low, Central, false, Some(Glide::W)), // ㅘ
+        10 => (Low, Front, false, Some(Glide::W)),  // ㅙ
+        11 => (Mid, Front, true, None),             // ㅚ
+        12 => (Mid, Back, true, Some(Glide::Y)),    // ㅛ
+        13 => (High, Back, true, None),             // ㅜ
+        14 => (Mid, Back, false, Some(Glide::W)),   // ㅝ
+        15 => (Mid, Front, false, Some(Glide::W)),  // ㅞ
+        16 => (High, Front, true, None),            // ㅟ
+        17 => (High, Back, true, Some(Glide::Y)),   // ㅠ
+        18 => (High, Back, false, None),            // ㅡ
+        19 => (High, Front, false, None),           // ㅢ, taken as its nucleus ㅣ
+        20 => (High, Front, false, None),           // ㅣ
+        _ => return None,
+    };
+    Some(Features::Vowel { height, backness, rounded, glide })
+}
+
+/// Returns how alike two syllables sound, from 0 to 1, comparing the
+/// features of their leads, vowels and tails, so ㅂ/ㅍ (one feature apart)
+/// score closer than ㅂ/ㅅ. Useful for ranking spelling suggestions and
+/// analyzing speech recognition errors.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use jamo::hangul::Hangul;
+/// use jamo::phonology::phonetic_similarity;
+/// let h = |c| Hangul::try_from(c).unwrap();
+/// assert_eq!(1.0, phonetic_similarity(&h('발'), &h('발')));
+/// assert!(phonetic_similarity(&h('발'), &h('팔')) > phonetic_similarity(&h('발'), &h('살')));
+/// assert!(phonetic_similarity(&h('게'), &h('개')) > phonetic_similarity(&h('게'), &h('구')));
+/// ```
+pub fn phonetic_similarity(a: &Hangul, b: &Hangul) -> f64 {
+    0.35 * jamo_similarity(a.lead(), b.lead())
+        + 0.45 * jamo_similarity(a.vowel(), b.vowel())
+        + 0.2 * jamo_similarity(a.tail(), b.tail())
+}
+
+fn jamo_similarity(a: &Jamo, b: &Jamo) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let same = |same: bool, weight: f64| if same { weight } else { 0.0 };
+    match (a.features(), b.features()) {
+        (Some(Features::Consonant { place: pa, manner: ma, phonation: ha }),
+         Some(Features::Consonant { place: pb, manner: mb, phonation: hb })) => {
+            same(pa == pb, 0.4) + same(ma == mb, 0.4) + same(ha == hb, 0.2)
+        }
+        (Some(Features::Vowel { height: ha, backness: ba, rounded: ra, glide: ga }),
+         Some(Features::Vowel { height: hb, backness: bb, rounded: rb, glide: gb })) => {
+            same(ha == hb, 0.35) + same(ba == bb, 0.35) + same(ra == rb, 0.2) + same(ga == gb, 0.1)
+        }
+        _ => 0.0,
+    }
+}
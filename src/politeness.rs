@@ -0,0 +1,237 @@
+//! Conversion of sentence-final endings between the formal polite style
+//! (합쇼체: 갑니다, 갑니까) and the informal polite style (해요체: 가요).
+//!
+//! Endings are rewritten by syllable and tail manipulation: the verb stem is
+//! recovered from one style and conjugated into the other. Stems whose
+//! conjugation is irregular (고맙다, 듣다, 모르다) are listed in a small table;
+//! others are conjugated regularly, so rare irregular verbs may come out wrong.
+
+use crate::blocks;
+use crate::hangul::decompose;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpeechLevel {
+    /// 합쇼체, the formal polite style: -ㅂ니다, -습니다, -ㅂ니까.
+    Hapsyo,
+    /// 해요체, the informal polite style: -아요, -어요.
+    Haeyo,
+}
+
+// Whole endings that do not follow the stem rules, in both styles.
+const ENDINGS: [(&str, &str, &str); 2] = [
+    // 합쇼체 statement, 합쇼체 question, 해요체
+    ("아닙니다", "아닙니까", "아니에요"),
+    ("합니다", "합니까", "해요"),
+];
+
+// Stems with an irregular 아/어 form, with that form.
+const IRREGULAR_STEMS: [(&str, &str); 28] = [
+    ("고맙", "고마워"), ("반갑", "반가워"), ("덥", "더워"), ("춥", "추워"), ("쉽", "쉬워"),
+    ("어렵", "어려워"), ("맵", "매워"), ("무겁", "무거워"), ("가볍", "가벼워"), ("즐겁", "즐거워"),
+    ("귀엽", "귀여워"), ("가깝", "가까워"), ("아름답", "아름다워"), ("돕", "도와"),
+    ("듣", "들어"), ("걷", "걸어"), ("낫", "나아"), ("짓", "지어"),
+    ("그렇", "그래"), ("어떻", "어때"), ("이렇", "이래"), ("저렇", "저래"),
+    ("모르", "몰라"), ("다르", "달라"), ("부르", "불러"), ("빠르", "빨라"), ("고르", "골라"),
+    ("하", "해"), ];
+
+const TERMINATORS: [char; 3] = ['.', '?', '!'];
+
+// Stems ending in ㄹ, which drop it before -ㅂ니다 (알다: 압니다). Every such
+// stem does; the list only picks 알 over 아 when 압니다 goes back to 해요체.
+const L_STEMS: [&str; 9] = ["알", "만들", "놀", "열", "팔", "울", "멀", "길", "힘들"];
+
+/// Returns `text` with the final word of each sentence converted to `level`.
+/// Sentences already in that style, or whose ending is not recognized, are
+/// left as they are.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::politeness::{convert, SpeechLevel};
+/// let formal = "저는 학생입니다. 밥을 먹습니까? 정말 고맙습니다";
+/// let informal = "저는 학생이에요. 밥을 먹어요? 정말 고마워요";
+/// assert_eq!(informal, convert(formal, SpeechLevel::Haeyo));
+/// assert_eq!(formal, convert(informal, SpeechLevel::Hapsyo));
+/// assert_eq!("학교에 가요. 친구가 와요.", convert("학교에 갑니다. 친구가 옵니다.", SpeechLevel::Haeyo));
+/// ```
+pub fn convert(text: &str, level: SpeechLevel) -> String {
+    let mut converted = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        let end = rest.find(TERMINATORS).unwrap_or(rest.len());
+        let sentence_end = rest[end..].find(|c| !TERMINATORS.contains(&c)).map_or(rest.len(), |i| end + i);
+        let (body, punctuation) = (&rest[..end], &rest[end..sentence_end]);
+        let word_start = body.trim_end().rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let word = body[word_start..].trim_end();
+        converted.push_str(&body[..word_start]);
+        match convert_word(word, level, punctuation.contains('?')) {
+            Some(word) => converted.push_str(&word),
+            None => converted.push_str(word),
+        }
+        converted.push_str(&body[word_start + word.len()..]);
+        converted.push_str(punctuation);
+        rest = &rest[sentence_end..];
+    }
+    converted
+}
+
+/// Returns the sentence-final `word` converted to `level`, asking a question
+/// when `question` is set, or `None` if its ending is not recognized.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::politeness::{convert_word, SpeechLevel};
+/// assert_eq!(Some("갑니까".to_string()), convert_word("가요", SpeechLevel::Hapsyo, true));
+/// assert_eq!(Some("알아요".to_string()), convert_word("압니다", SpeechLevel::Haeyo, false));
+/// assert_eq!(Some("삽니다".to_string()), convert_word("살아요", SpeechLevel::Hapsyo, false));
+/// assert_eq!(Some("의자예요".to_string()), convert_word("의자입니다", SpeechLevel::Haeyo, false));
+/// assert_eq!(None, convert_word("간다", SpeechLevel::Haeyo, false));
+/// ```
+pub fn convert_word(word: &str, level: SpeechLevel, question: bool) -> Option<String> {
+    match level {
+        SpeechLevel::Haeyo => to_haeyo(word),
+        SpeechLevel::Hapsyo => to_hapsyo(word, question),
+    }
+}
+
+fn to_haeyo(word: &str) -> Option<String> {
+    if let Some(stem) = word.strip_suffix("십시오") {
+        return Some(format!("{}세요", stem));
+    }
+    for &(statement, asking, haeyo) in &ENDINGS {
+        for ending in [statement, asking] {
+            if let Some(stem) = word.strip_suffix(ending) {
+                return Some(format!("{}{}", stem, haeyo));
+            }
+        }
+    }
+    for ending in ["입니다", "입니까"] {
+        if let Some(noun) = word.strip_suffix(ending) {
+            let vowel_final = noun.chars().last().and_then(decompose).is_some_and(|(_, _, t)| t == 0);
+            return Some(format!("{}{}", noun, if vowel_final { "예요" } else { "이에요" }));
+        }
+    }
+    let stem = formal_stem(word)?;
+    Some(format!("{}요", infinitive(&stem)?))
+}
+
+/// Returns the verb stem of a word ending in -습니다, -ㅂ니다 or -ㅂ시다 and
+/// their questions.
+fn formal_stem(word: &str) -> Option<String> {
+    for ending in ["습니다", "습니까", "읍시다"] {
+        if let Some(stem) = word.strip_suffix(ending).filter(|s| !s.is_empty()) {
+            return Some(stem.to_string());
+        }
+    }
+    let mut chars = ["니다", "니까", "시다"].iter().find_map(|e| word.strip_suffix(e))?.chars().collect::<Vec<char>>();
+    let (l, v, t) = chars.last().copied().and_then(decompose)?;
+    if t != 17 {
+        return None;
+    }
+    *chars.last_mut()? = syllable(l, v, 0)?;
+    let stem = chars.into_iter().collect::<String>();
+    let with_l = with_last_tail(&stem, 8)?;
+    Some(if L_STEMS.contains(&with_l.as_str()) { with_l } else { stem })
+}
+
+/// Returns the 아/어 form of `stem`: 가 → 가, 먹 → 먹어, 보 → 봐.
+fn infinitive(stem: &str) -> Option<String> {
+    if let Some((irregular, form)) = IRREGULAR_STEMS.iter().find(|(s, _)| stem.ends_with(s)) {
+        return Some(format!("{}{}", &stem[..stem.len() - irregular.len()], form));
+    }
+    let mut chars = stem.chars().collect::<Vec<char>>();
+    let (l, v, t) = chars.last().copied().and_then(decompose)?;
+    let bright = |v: u8| matches!(v, 0 | 2 | 8);
+    if t != 0 {
+        chars.push(if t != 20 && bright(v) { '아' } else { '어' });
+        return Some(chars.into_iter().collect());
+    }
+    let previous_bright = chars.iter().rev().nth(1).copied().and_then(decompose).is_some_and(|(_, v, _)| bright(v));
+    let contracted = match v {
+        8 => 9,
+        13 => 14,
+        11 => 10,
+        20 => 6,
+        18 if previous_bright => 0,
+        18 => 4,
+        16 | 19 => {
+            chars.push('어');
+            return Some(chars.into_iter().collect());
+        }
+        v => v,
+    };
+    *chars.last_mut()? = syllable(l, contracted, 0)?;
+    Some(chars.into_iter().collect())
+}
+
+fn to_hapsyo(word: &str, question: bool) -> Option<String> {
+    let ending = if question { "니까" } else { "니다" };
+    if let Some(stem) = word.strip_suffix("세요") {
+        return Some(format!("{}십{}", stem, ending));
+    }
+    for &(statement, asking, haeyo) in &ENDINGS {
+        if let Some(stem) = word.strip_suffix(haeyo) {
+            return Some(format!("{}{}", stem, if question { asking } else { statement }));
+        }
+    }
+    for copula in ["이에요", "예요"] {
+        if let Some(noun) = word.strip_suffix(copula) {
+            return Some(format!("{}입{}", noun, ending));
+        }
+    }
+    let form = word.strip_suffix('요').filter(|f| !f.is_empty())?;
+    let stem = informal_stem(form)?;
+    let mut chars = stem.chars().collect::<Vec<char>>();
+    let (l, v, t) = chars.last().copied().and_then(decompose)?;
+    match t {
+        0 => *chars.last_mut()? = syllable(l, v, 17)?,
+        8 => *chars.last_mut()? = syllable(l, v, 17)?,
+        _ => return Some(format!("{}습{}", stem, ending)),
+    }
+    Some(format!("{}{}", chars.into_iter().collect::<String>(), ending))
+}
+
+/// Returns the verb stem of an 아/어 form: 먹어 → 먹, 가 → 가, 봐 → 보.
+fn informal_stem(form: &str) -> Option<String> {
+    // A one-syllable stem must make up the whole form: 만들어 is not from 듣다.
+    let irregular = IRREGULAR_STEMS.iter().find(|(stem, f)| {
+        form == *f || form.ends_with(f) && (stem.chars().count() > 1 || *stem == "하")
+    });
+    if let Some((stem, irregular)) = irregular {
+        return Some(format!("{}{}", &form[..form.len() - irregular.len()], stem));
+    }
+    let mut chars = form.chars().collect::<Vec<char>>();
+    if chars.len() > 1 && matches!(chars.last(), Some('아' | '어')) {
+        chars.pop();
+        return Some(chars.into_iter().collect());
+    }
+    let (l, v, t) = chars.last().copied().and_then(decompose)?;
+    if t != 0 {
+        return None;
+    }
+    let multi_syllable = chars.len() > 1;
+    let vowel = match (v, l) {
+        (9, _) => 8,
+        (14, _) => 13,
+        (10, _) => 11,
+        (6, _) if multi_syllable => 20,
+        // ㅡ stems: 쓰다 → 써, 바쁘다 → 바빠.
+        (4, 10) | (4, 15) | (4, 4) | (4, 1) => 18,
+        (0 | 4, 8 | 17) if multi_syllable => 18,
+        (v, _) => v,
+    };
+    *chars.last_mut()? = syllable(l, vowel, 0)?;
+    Some(chars.into_iter().collect())
+}
+
+fn syllable(lead: u8, vowel: u8, tail: u8) -> Option<char> {
+    blocks::compose_indices(lead as usize, vowel as usize, tail as usize).ok()
+}
+
+fn with_last_tail(word: &str, tail: u8) -> Option<String> {
+    let mut chars = word.chars().collect::<Vec<char>>();
+    let (l, v, _) = chars.last().copied().and_then(decompose)?;
+    *chars.last_mut()? = syllable(l, v, tail)?;
+    Some(chars.into_iter().collect())
+}
@@ -0,0 +1,131 @@
+//! Protocol-buffer messages for analysis results, so services in other
+//! languages can consume them over gRPC. They match `proto/jamo.proto` in the
+//! repository and carry the same fields as [`crate::json`].
+//!
+//! The derives are written by hand rather than generated, so the example
+//! below checks every field's tag, type and label against the schema.
+//!
+//! ```
+//! fn fields(source: &str, field: impl Fn(&str, &[&str]) -> Option<String>) -> Vec<String> {
+//!     let (mut message, mut fields) = (String::new(), vec![]);
+//!     let lines = source.lines().map(|l| l.split("//").next().unwrap().trim()).collect::<Vec<_>>();
+//!     for (i, line) in lines.iter().enumerate() {
+//!         let words = line.split(|c: char| !c.is_alphanumeric() && c != '_')
+//!             .filter(|w| !w.is_empty()).collect::<Vec<_>>();
+//!         match words.as_slice() {
+//!             ["message" | "pub", "struct", name] | ["message", name] => message = name.to_string(),
+//!             _ => fields.extend(field(lines.get(i + 1).unwrap_or(&""), &words).map(|f| format!("{}.{}", message, f))),
+//!         }
+//!     }
+//!     fields.sort();
+//!     fields
+//! }
+//!
+//! let schema = fields(include_str!("../proto/jamo.proto"), |_, words| match words {
+//!     [label @ ("optional" | "repeated"), ty, name, tag] => Some(format!("{} {} {} {}", name, label, ty, tag)),
+//!     [ty, name, tag] if tag.parse::<u32>().is_ok() => Some(format!("{} - {} {}", name, ty, tag)),
+//!     _ => None,
+//! });
+//! let derived = fields(include_str!("proto.rs"), |next, words| {
+//!     let name = next.strip_prefix("pub ")?.split(':').next()?;
+//!     match words {
+//!         ["prost", ty, label, "tag", tag] => Some(format!("{} {} {} {}", name, label, ty, tag)),
+//!         ["prost", ty, "tag", tag] => Some(format!("{} - {} {}", name, ty, tag)),
+//!         _ => None,
+//!     }
+//! });
+//! let schema = schema.iter().map(|f| f.replace(" SyllableRecord ", " message ").replace(" FiredRule ", " message "));
+//! assert_eq!(schema.collect::<Vec<_>>(), derived);
+//! ```
+
+use crate::hangul::{self, KoreanSentence};
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SyllableRecord {
+    #[prost(uint64, tag = "1")]
+    pub char_index: u64,
+    #[prost(uint64, tag = "2")]
+    pub byte_start: u64,
+    #[prost(uint64, tag = "3")]
+    pub byte_end: u64,
+    #[prost(string, tag = "4")]
+    pub original: String,
+    #[prost(string, optional, tag = "5")]
+    pub lead: Option<String>,
+    #[prost(string, optional, tag = "6")]
+    pub vowel: Option<String>,
+    #[prost(string, optional, tag = "7")]
+    pub tail: Option<String>,
+    #[prost(string, tag = "8")]
+    pub roman: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FiredRule {
+    #[prost(uint64, tag = "1")]
+    pub letter_index: u64,
+    #[prost(string, tag = "2")]
+    pub rule: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Sentence {
+    #[prost(uint32, tag = "1")]
+    pub schema_version: u32,
+    #[prost(string, tag = "2")]
+    pub text: String,
+    #[prost(string, tag = "3")]
+    pub roman: String,
+    #[prost(string, tag = "4")]
+    pub pronounced: String,
+    #[prost(message, repeated, tag = "5")]
+    pub records: Vec<SyllableRecord>,
+    #[prost(message, repeated, tag = "6")]
+    pub rules: Vec<FiredRule>,
+}
+
+impl From<&hangul::SyllableRecord> for SyllableRecord {
+    fn from(record: &hangul::SyllableRecord) -> Self {
+        Self {
+            char_index: record.char_index as u64,
+            byte_start: record.byte_range.start as u64,
+            byte_end: record.byte_range.end as u64,
+            original: record.original.to_string(),
+            lead: record.lead.map(String::from),
+            vowel: record.vowel.map(String::from),
+            tail: record.tail.map(String::from),
+            roman: record.roman.clone(),
+        }
+    }
+}
+
+impl From<&hangul::FiredRule> for FiredRule {
+    fn from(fired: &hangul::FiredRule) -> Self {
+        Self { letter_index: fired.index as u64, rule: fired.rule.to_string() }
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// use jamo::proto::Sentence;
+/// use prost::Message;
+/// let message = Sentence::from(&KoreanSentence::new("좋아").applied());
+/// let decoded = Sentence::decode(&message.encode_to_vec()[..]).unwrap();
+/// assert_eq!("joa", decoded.roman);
+/// assert_eq!("h-deletion", decoded.rules[0].rule);
+/// ```
+impl From<&KoreanSentence> for Sentence {
+    fn from(sentence: &KoreanSentence) -> Self {
+        let records = sentence.records();
+        Self {
+            schema_version: crate::json::SCHEMA_VERSION,
+            text: records.iter().map(|r| r.original).collect(),
+            roman: sentence.roman(),
+            pronounced: sentence.pronounced(),
+            records: records.iter().map(SyllableRecord::from).collect(),
+            rules: sentence.fired_rules().iter().map(FiredRule::from).collect(),
+        }
+    }
+}
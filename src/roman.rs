@@ -0,0 +1,466 @@
+use std::ops::Range;
+
+use crate::blocks;
+use crate::hangul::{decompose, KoreanSentence, Letter, LEAD_DICT, TAIL_DICT, VOWEL_DICT};
+use crate::spell::Dictionary;
+
+const APOSTROPHES: &str = "'’‘ʼʻ`";
+
+/// Folds romanized text for lookup: lowercases, strips diacritics such as the
+/// McCune–Reischauer breves, and removes apostrophes.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::roman::fold_roman;
+/// assert_eq!("chon", fold_roman("Chŏn"));
+/// assert_eq!("pyongyang", fold_roman("P'yŏngyang"));
+/// assert_eq!("hangul", fold_roman("Hangu\u{306}l"));
+/// ```
+pub fn fold_roman(s: &str) -> String {
+    s.chars()
+        .filter(|c| !APOSTROPHES.contains(*c) && !is_combining_mark(*c))
+        .flat_map(|c| strip_diacritic(c).to_lowercase())
+        .collect()
+}
+
+/// Compares romanized strings ignoring case, diacritics and apostrophes.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::roman::roman_eq_ignore_case;
+/// assert!(roman_eq_ignore_case("Choayo", "choayo"));
+/// assert!(roman_eq_ignore_case("chŏn", "chon"));
+/// assert!(!roman_eq_ignore_case("chon", "jon"));
+/// ```
+pub fn roman_eq_ignore_case(a: &str, b: &str) -> bool {
+    fold_roman(a) == fold_roman(b)
+}
+
+/// Uppercases the first letter of `word`, leaving the rest as is.
+pub fn capitalize(word: &str) -> String {
+    match word.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((i, c)) => format!("{}{}{}", &word[..i], c.to_uppercase(), &word[i + c.len_utf8()..]),
+        None => word.to_string(),
+    }
+}
+
+/// Capitalizes each space-separated word of a romanized proper noun.
+///
+/// Only the first part of a hyphenated word is capitalized, as in given names,
+/// and apostrophes never start a new word.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::roman::title_case;
+/// assert_eq!("Hong Gil-dong", title_case("hong gil-dong"));
+/// assert_eq!("P'yŏngyang", title_case("p'yŏngyang"));
+/// ```
+pub fn title_case(s: &str) -> String {
+    s.split(' ').map(capitalize).collect::<Vec<String>>().join(" ")
+}
+
+/// Capitalizes the start of the text and of every sentence after `.`, `?` or `!`.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::roman::sentence_case;
+/// assert_eq!("Annyeong. Ŏdi ka?", sentence_case("annyeong. ŏdi ka?"));
+/// ```
+pub fn sentence_case(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut at_start = true;
+    let mut after_end = false;
+    for c in s.chars() {
+        if at_start && c.is_alphabetic() {
+            result.extend(c.to_uppercase());
+            at_start = false;
+            continue;
+        }
+        if after_end && c.is_whitespace() {
+            at_start = true;
+        }
+        after_end = ".?!".contains(c);
+        result.push(c);
+    }
+    result
+}
+
+/// A romanization system for [`romanize`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RomanizationSystem {
+    /// The crate's letter-by-letter transliteration, as in [`KoreanSentence::roman`].
+    Jamo,
+    /// Revised Romanization of Korean (2000).
+    Revised,
+    /// McCune–Reischauer, with breves and apostrophes.
+    McCuneReischauer,
+    /// Yale romanization for Middle Korean, letter by letter. Obsolete jamo
+    /// such as ㆍ "o", ㅿ "z" and ㆁ "ng" are read from conjoining or
+    /// compatibility jamo; tone dots are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// use jamo::roman::{romanize, RomanizationSystem};
+    /// let sentence = KoreanSentence::new("나랏〮 말〯ᄊᆞ미〮 듕귁〮에〮 달아〮");
+    /// assert_eq!("nalas malssomi tywungkwuykey tala", romanize(&sentence, RomanizationSystem::Yale));
+    /// ```
+    Yale,
+}
+
+const RR_LEADS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s",
+    "ss", "", "j", "jj", "ch", "k", "t", "p", "h", ];
+const RR_VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa",
+    "wae", "oe", "yo", "u", "wo", "we", "wi", "yu", "eu", "ui",
+    "i", ];
+const MR_LEADS: [&str; 19] = [
+    "k", "kk", "n", "t", "tt", "r", "m", "p", "pp", "s",
+    "ss", "", "ch", "tch", "ch'", "k'", "t'", "p'", "h", ];
+const MR_VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "ŏ", "e", "yŏ", "ye", "o", "wa",
+    "wae", "oe", "yo", "u", "wŏ", "we", "wi", "yu", "ŭ", "ŭi",
+    "i", ];
+// Sorted by char, covering the conjoining jamo of 15th-century texts and the
+// compatibility jamo.
+const YALE: [(char, &str); 146] = [
+    ('\u{1100}', "k"), ('\u{1101}', "kk"), ('\u{1102}', "n"), ('\u{1103}', "t"), ('\u{1104}', "tt"), ('\u{1105}', "l"),
+    ('\u{1106}', "m"), ('\u{1107}', "p"), ('\u{1108}', "pp"), ('\u{1109}', "s"), ('\u{110a}', "ss"), ('\u{110b}', ""),
+    ('\u{110c}', "c"), ('\u{110d}', "cc"), ('\u{110e}', "ch"), ('\u{110f}', "kh"), ('\u{1110}', "th"), ('\u{1111}', "ph"),
+    ('\u{1112}', "h"), ('\u{111e}', "pk"), ('\u{1120}', "pt"), ('\u{1121}', "ps"), ('\u{1122}', "psk"), ('\u{1123}', "pst"),
+    ('\u{1127}', "pc"), ('\u{1129}', "pth"), ('\u{112b}', "W"), ('\u{112d}', "sk"), ('\u{112f}', "st"), ('\u{1132}', "sp"),
+    ('\u{1140}', "z"), ('\u{114c}', "ng"), ('\u{1158}', "hh"), ('\u{1159}', "q"), ('\u{1161}', "a"), ('\u{1162}', "ay"),
+    ('\u{1163}', "ya"), ('\u{1164}', "yay"), ('\u{1165}', "e"), ('\u{1166}', "ey"), ('\u{1167}', "ye"), ('\u{1168}', "yey"),
+    ('\u{1169}', "wo"), ('\u{116a}', "wa"), ('\u{116b}', "way"), ('\u{116c}', "woy"), ('\u{116d}', "ywo"), ('\u{116e}', "wu"),
+    ('\u{116f}', "we"), ('\u{1170}', "wey"), ('\u{1171}', "wuy"), ('\u{1172}', "ywu"), ('\u{1173}', "u"), ('\u{1174}', "uy"),
+    ('\u{1175}', "i"), ('\u{1188}', "ywoy"), ('\u{1194}', "ywuy"), ('\u{119e}', "o"), ('\u{11a1}', "oy"), ('\u{11a8}', "k"),
+    ('\u{11a9}', "kk"), ('\u{11aa}', "ks"), ('\u{11ab}', "n"), ('\u{11ac}', "nc"), ('\u{11ad}', "nh"), ('\u{11ae}', "t"),
+    ('\u{11af}', "l"), ('\u{11b0}', "lk"), ('\u{11b1}', "lm"), ('\u{11b2}', "lp"), ('\u{11b3}', "ls"), ('\u{11b4}', "lth"),
+    ('\u{11b5}', "lph"), ('\u{11b6}', "lh"), ('\u{11b7}', "m"), ('\u{11b8}', "p"), ('\u{11b9}', "ps"), ('\u{11ba}', "s"),
+    ('\u{11bb}', "ss"), ('\u{11bc}', "ng"), ('\u{11bd}', "c"), ('\u{11be}', "ch"), ('\u{11bf}', "kh"), ('\u{11c0}', "th"),
+    ('\u{11c1}', "ph"), ('\u{11c2}', "h"), ('\u{11eb}', "z"), ('\u{11f0}', "ng"), ('\u{11f9}', "q"), ('ㄱ', "k"),
+    ('ㄲ', "kk"), ('ㄳ', "ks"), ('ㄴ', "n"), ('ㄵ', "nc"), ('ㄶ', "nh"), ('ㄷ', "t"),
+    ('ㄸ', "tt"), ('ㄹ', "l"), ('ㄺ', "lk"), ('ㄻ', "lm"), ('ㄼ', "lp"), ('ㄽ', "ls"),
+    ('ㄾ', "lth"), ('ㄿ', "lph"), ('ㅀ', "lh"), ('ㅁ', "m"), ('ㅂ', "p"), ('ㅃ', "pp"),
+    ('ㅄ', "ps"), ('ㅅ', "s"), ('ㅆ', "ss"), ('ㅇ', ""), ('ㅈ', "c"), ('ㅉ', "cc"),
+    ('ㅊ', "ch"), ('ㅋ', "kh"), ('ㅌ', "th"), ('ㅍ', "ph"), ('ㅎ', "h"), ('ㅏ', "a"),
+    ('ㅐ', "ay"), ('ㅑ', "ya"), ('ㅒ', "yay"), ('ㅓ', "e"), ('ㅔ', "ey"), ('ㅕ', "ye"),
+    ('ㅖ', "yey"), ('ㅗ', "wo"), ('ㅘ', "wa"), ('ㅙ', "way"), ('ㅚ', "woy"), ('ㅛ', "ywo"),
+    ('ㅜ', "wu"), ('ㅝ', "we"), ('ㅞ', "wey"), ('ㅟ', "wuy"), ('ㅠ', "ywu"), ('ㅡ', "u"),
+    ('ㅢ', "uy"), ('ㅣ', "i"), ('ㅸ', "W"), ('ㅿ', "z"), ('ㆁ', "ng"), ('ㆆ', "q"),
+    ('ㆍ', "o"), ('ㆎ', "oy"), ];
+const TONE_DOTS: [char; 2] = ['\u{302e}', '\u{302f}'];
+// Both systems write a tail by the sound it neutralizes to.
+const TAIL_SOUNDS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k",
+    "m", "l", "l", "l", "p", "l", "m", "p", "p", "t",
+    "t", "ng", "t", "t", "k", "t", "p", "t", ];
+const VOICING_TAILS: [&str; 5] = ["", "n", "l", "m", "ng"];
+
+/// Romanizes the letters of `sentence` as they are; apply the rules first to
+/// romanize the pronunciation. Lead ㄹ after ㄹ is written "l", and
+/// McCune–Reischauer voices ㄱ, ㄷ, ㅂ and ㅈ after a vowel or sonorant.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// use jamo::roman::{romanize, RomanizationSystem};
+/// let sentence = KoreanSentence::new("부산 대구 서울 실라");
+/// assert_eq!("busan daegu seoul silla", romanize(&sentence, RomanizationSystem::Revised));
+/// assert_eq!("pusan taegu sŏul silla", romanize(&sentence, RomanizationSystem::McCuneReischauer));
+/// ```
+pub fn romanize(sentence: &KoreanSentence, system: RomanizationSystem) -> String {
+    let (leads, vowels) = match system {
+        RomanizationSystem::Jamo => return sentence.roman(),
+        RomanizationSystem::Revised => (&RR_LEADS, &RR_VOWELS),
+        RomanizationSystem::McCuneReischauer => (&MR_LEADS, &MR_VOWELS),
+        RomanizationSystem::Yale => return yale(sentence),
+    };
+    let mut result = String::new();
+    let mut previous_tail = None;
+    for letter in sentence.letters() {
+        match letter {
+            Letter::HangulLetter(h) => {
+                let voiced = previous_tail.is_some_and(|t| VOICING_TAILS.contains(&t));
+                let lead = match (system, h.lead().index()) {
+                    (_, 5) if previous_tail == Some("l") => "l",
+                    (RomanizationSystem::McCuneReischauer, 0) if voiced => "g",
+                    (RomanizationSystem::McCuneReischauer, 3) if voiced => "d",
+                    (RomanizationSystem::McCuneReischauer, 7) if voiced => "b",
+                    (RomanizationSystem::McCuneReischauer, 12) if voiced => "j",
+                    (_, i) => leads[i],
+                };
+                let tail = TAIL_SOUNDS[h.tail().index()];
+                result.push_str(lead);
+                result.push_str(vowels[h.vowel().index()]);
+                result.push_str(tail);
+                previous_tail = Some(tail);
+            }
+            Letter::OtherLetter(c) => {
+                result.push(*c);
+                previous_tail = None;
+            }
+        }
+    }
+    result
+}
+
+fn yale(sentence: &KoreanSentence) -> String {
+    let mut result = String::new();
+    for letter in sentence.letters() {
+        let chars = match letter {
+            Letter::HangulLetter(h) => vec![
+                0x1100 + h.lead().index(), 0x1161 + h.vowel().index(), 0x11a7 + h.tail().index(), ]
+                .into_iter()
+                .filter(|&code| code != 0x11a7)
+                .filter_map(|code| char::from_u32(code as u32))
+                .collect(),
+            Letter::OtherLetter(c) => vec![*c],
+        };
+        for c in chars.into_iter().filter(|c| !TONE_DOTS.contains(c)) {
+            match YALE.binary_search_by_key(&c, |&(k, _)| k) {
+                Ok(i) => result.push_str(YALE[i].1),
+                Err(_) => result.push(c),
+            }
+        }
+    }
+    result
+}
+
+/// A Hangul reading of romanized text, from [`from_roman_candidates`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RomanCandidate {
+    pub hangul: String,
+    /// Higher is more likely; scores only compare within one call.
+    pub score: f64,
+    /// The estimated probability that this is the intended reading, from 0
+    /// to 1: the candidate's share of the score of all readings of each word.
+    pub confidence: f64,
+}
+
+// Spellings accepted besides the crate's own, mostly from Revised Romanization.
+const EXTRA_LEADS: [(&str, usize); 2] = [("jj", 13), ("l", 5)];
+const EXTRA_VOWELS: [(&str, usize); 2] = [("wo", 14), ("ui", 19)];
+const EXTRA_TAILS: [(&str, usize); 4] = [("k", 1), ("t", 7), ("l", 8), ("p", 17)];
+// Parses kept per word, to bound the search on long runs of letters.
+const MAX_PARSES: usize = 256;
+
+/// Reads romanized text back into Hangul, returning up to `limit` readings
+/// best first. The crate's own romanization and Revised Romanization are
+/// both accepted; letters that fit no syllable are kept as they are.
+///
+/// Readings with fewer syllables score higher, and a tail followed by a
+/// vowel or a silent ㅇ lead after a tail scores lower, since romanization
+/// usually reflects where the syllables break.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::roman::from_roman_candidates;
+/// let candidates = from_roman_candidates("hangeul", 3);
+/// assert_eq!("한글", candidates[0].hangul);
+/// assert!(candidates.iter().any(|c| c.hangul == "항을"));
+/// assert_eq!("강 3", from_roman_candidates("gang 3", 1)[0].hangul);
+/// assert_eq!(1.0, from_roman_candidates("gang", 1)[0].confidence);
+/// // Long runs that never fully parse are kept as they are, quickly.
+/// let unparsable = format!("{}x", "anga".repeat(50));
+/// assert_eq!(unparsable, from_roman_candidates(&unparsable, 5)[0].hangul);
+/// ```
+pub fn from_roman_candidates(s: &str, limit: usize) -> Vec<RomanCandidate> {
+    candidates(s, limit, None)
+}
+
+/// Like [`from_roman_candidates`], weighting the readings of each word by
+/// their frequency in `dictionary` plus one, so known words win over
+/// plausible but unattested syllable splits.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::roman::from_roman_candidates_with;
+/// use jamo::spell::Dictionary;
+/// let dict = Dictionary::from_word_list("항을 50\n");
+/// let candidates = from_roman_candidates_with("hangeul", 2, &dict);
+/// assert_eq!("항을", candidates[0].hangul);
+/// assert!(candidates[0].confidence > 0.8);
+/// ```
+pub fn from_roman_candidates_with(s: &str, limit: usize, dictionary: &Dictionary) -> Vec<RomanCandidate> {
+    candidates(s, limit, Some(dictionary))
+}
+
+fn candidates(s: &str, limit: usize, dictionary: Option<&Dictionary>) -> Vec<RomanCandidate> {
+    let s = s.to_lowercase();
+    let mut beam = vec![(String::new(), 1.0, 1.0)];
+    let mut rest = s.as_str();
+    while !rest.is_empty() {
+        let letters = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        let (token, readings) = if letters > 0 {
+            (letters, word_readings(&rest[..letters], dictionary))
+        } else {
+            let c = rest.chars().next().map_or(1, char::len_utf8);
+            (c, vec![(rest[..c].to_string(), 1.0, 1.0)])
+        };
+        let mut next = beam.iter()
+            .flat_map(|(hangul, score, confidence)| readings.iter().map(move |(r, s, c)| {
+                (format!("{}{}", hangul, r), score * s, confidence * c)
+            }))
+            .collect::<Vec<(String, f64, f64)>>();
+        next.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        next.truncate(limit.max(1));
+        beam = next;
+        rest = &rest[token..];
+    }
+    beam.truncate(limit);
+    beam.into_iter()
+        .map(|(hangul, score, confidence)| RomanCandidate { hangul, score, confidence })
+        .collect()
+}
+
+// Letter sequences common in romanized Korean and rare in English.
+const KOREAN_MARKERS: [&str; 14] = ["eo", "eu", "ae", "oe", "ui", "yeo", "yu", "ya", "yo", "ng", "kk", "tt", "pp", "jj"];
+// Longer runs of letters are not read, so one long token cannot stall detection.
+const MAX_ROMANIZED_WORD: usize = 64;
+
+/// Finds the byte ranges of `text` that are likely romanized Korean, such as
+/// "annyeonghaseyo" in English text, as a pre-pass for [`from_roman_candidates`].
+///
+/// A word counts when all of it reads as Hangul syllables without odd
+/// splits and it either has a spelling typical of romanized Korean ("eo",
+/// "ng", "kk" and the like) or is three or more syllables of clean reading.
+/// Neighboring words separated by spaces or hyphens form one range. Runs of
+/// more than 64 letters are never counted. This is a heuristic: short
+/// English words that happen to spell syllables pass too.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::roman::detect_romanized_korean;
+/// let text = "She said annyeonghaseyo and then kamsahamnida to me.";
+/// let ranges = detect_romanized_korean(text);
+/// assert_eq!(vec!["annyeonghaseyo", "kamsahamnida"], ranges.iter().map(|r| &text[r.clone()]).collect::<Vec<_>>());
+/// assert_eq!(vec![5..15], detect_romanized_korean("from Seoul-eseo"));
+/// assert!(detect_romanized_korean(&"anga".repeat(1_000)).is_empty());
+/// ```
+pub fn detect_romanized_korean(text: &str) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    let mut at = 0;
+    for word in text.split(|c: char| !c.is_ascii_alphabetic()) {
+        let range = at..at + word.len();
+        at = range.end + text[range.end..].chars().next().map_or(0, char::len_utf8);
+        if word.is_empty() || word.len() > MAX_ROMANIZED_WORD || !looks_romanized(&word.to_lowercase()) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if text[last.end..range.start].chars().all(|c| c == ' ' || c == '-') => last.end = range.end,
+            _ => ranges.push(range),
+        }
+    }
+    ranges
+}
+
+fn looks_romanized(word: &str) -> bool {
+    let best = word_readings(word, None).into_iter()
+        .filter(|(hangul, _, _)| hangul.chars().all(|c| decompose(c).is_some()))
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    let (hangul, score) = match best {
+        Some((hangul, score, _)) => (hangul, score),
+        None => return false,
+    };
+    let syllables = hangul.chars().count();
+    let clean = score.powf(1.0 / syllables as f64) >= 0.8;
+    clean && (KOREAN_MARKERS.iter().any(|m| word.contains(m)) || syllables >= 3)
+}
+
+/// Returns the distinct readings of a run of letters with their scores and
+/// shares of the total score.
+fn word_readings(word: &str, dictionary: Option<&Dictionary>) -> Vec<(String, f64, f64)> {
+    let mut parses = vec![];
+    let mut dead = vec![[false; 2]; word.len() + 1];
+    parse_roman(word, 0, false, &mut String::new(), 1.0, &mut parses, &mut dead);
+    if parses.is_empty() {
+        return vec![(word.to_string(), 1.0, 1.0)];
+    }
+    let mut readings: Vec<(String, f64)> = vec![];
+    for (hangul, mut score) in parses {
+        if let Some(dictionary) = dictionary {
+            score *= (dictionary.frequency(&hangul).unwrap_or(0) + 1) as f64;
+        }
+        match readings.iter_mut().find(|(h, _)| *h == hangul) {
+            Some(reading) => reading.1 = reading.1.max(score),
+            None => readings.push((hangul, score)),
+        }
+    }
+    let total = readings.iter().map(|(_, score)| score).sum::<f64>();
+    readings.into_iter().map(|(hangul, score)| (hangul, score, score / total)).collect()
+}
+
+/// Pushes the readings of `word` from `at` onto `readings`, returning whether
+/// there were any. Positions with no reading are marked in `dead` and not
+/// searched again, which keeps the search polynomial when nothing parses.
+fn parse_roman(word: &str, at: usize, after_tail: bool, current: &mut String, score: f64,
+               readings: &mut Vec<(String, f64)>, dead: &mut [[bool; 2]]) -> bool {
+    if at == word.len() {
+        readings.push((current.clone(), score));
+        return true;
+    }
+    if dead[at][after_tail as usize] {
+        return false;
+    }
+    let mut found = false;
+    let leads = LEAD_DICT.iter().copied().enumerate().map(|(i, l)| (l, i)).chain(EXTRA_LEADS);
+    for (lead, l) in leads.filter(|(lead, _)| word[at..].starts_with(lead)) {
+        let at = at + lead.len();
+        let vowels = VOWEL_DICT.iter().copied().enumerate().map(|(i, v)| (v, i)).chain(EXTRA_VOWELS);
+        for (vowel, v) in vowels.filter(|(vowel, _)| word[at..].starts_with(vowel)) {
+            let at = at + vowel.len();
+            let tails = TAIL_DICT.iter().copied().enumerate().map(|(i, t)| (t, i)).chain(EXTRA_TAILS);
+            for (tail, t) in tails.filter(|(tail, _)| word[at..].starts_with(tail)) {
+                if readings.len() >= MAX_PARSES {
+                    return true;
+                }
+                let next = at + tail.len();
+                let mut weight = 0.9;
+                if lead.is_empty() && !current.is_empty() {
+                    weight *= if after_tail { 0.5 } else { 0.8 };
+                }
+                if !tail.is_empty() && word[next..].starts_with(|c: char| "aeiouwy".contains(c)) {
+                    weight *= 0.3;
+                }
+                if let Ok(c) = blocks::compose_indices(l, v, t) {
+                    current.push(c);
+                    found |= parse_roman(word, next, !tail.is_empty(), current, score * weight, readings, dead);
+                    current.pop();
+                }
+            }
+        }
+    }
+    dead[at][after_tail as usize] = !found;
+    found
+}
+
+fn is_combining_mark(c: char) -> bool {
+    ('\u{300}'..='\u{36f}').contains(&c)
+}
+
+fn strip_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' => 'a',
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' => 'A',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' => 'e',
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' => 'E',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ĭ' => 'i',
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ī' | 'Ĭ' => 'I',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ō' | 'ŏ' => 'o',
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ō' | 'Ŏ' => 'O',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ŭ' => 'u',
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ū' | 'Ŭ' => 'U',
+        _ => c,
+    }
+}
@@ -0,0 +1,449 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::hangul::{is_lead_pattern, is_tail_pattern};
+
+/// What a rule puts in the tail or lead position it rewrites.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleOutput {
+    /// Leaves the jamo as it is.
+    Keep,
+    /// Puts the jamo with this romanization, e.g. `""` to delete it.
+    Set(Cow<'static, str>),
+    /// Puts the old tail, as in 연음화.
+    Tail,
+    /// Puts the old lead.
+    Lead,
+}
+
+/// A rewrite of the tail of a syllable and the lead of the next one.
+///
+/// Jamo are written in the crate's romanization (ㄹ is "r", ㅇ as a lead is
+/// ""), and a pattern of `"*"` matches anything. A rule whose output has no
+/// jamo in its position does not fire.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub name: Cow<'static, str>,
+    pub tail: Cow<'static, str>,
+    pub lead: Cow<'static, str>,
+    pub new_tail: RuleOutput,
+    pub new_lead: RuleOutput,
+}
+
+impl Rule {
+    pub fn matches(&self, tail: &str, lead: &str) -> bool {
+        (self.tail == "*" || self.tail == tail) && (self.lead == "*" || self.lead == lead)
+    }
+
+    /// Returns the new tail and lead for the matched `tail` and `lead`.
+    pub fn rewrite<'a>(&'a self, tail: &'a str, lead: &'a str) -> (&'a str, &'a str) {
+        let output = |o: &'a RuleOutput, keep: &'a str| match o {
+            RuleOutput::Keep => keep,
+            RuleOutput::Set(s) => s,
+            RuleOutput::Tail => tail,
+            RuleOutput::Lead => lead,
+        };
+        (output(&self.new_tail, tail), output(&self.new_lead, lead))
+    }
+}
+
+const fn rule(name: &'static str, tail: &'static str, lead: &'static str,
+              new_tail: RuleOutput, new_lead: RuleOutput) -> Rule {
+    Rule { name: Cow::Borrowed(name), tail: Cow::Borrowed(tail), lead: Cow::Borrowed(lead), new_tail, new_lead }
+}
+
+const fn set(s: &'static str) -> RuleOutput {
+    RuleOutput::Set(Cow::Borrowed(s))
+}
+
+const H_DELETION: Rule = rule("h-deletion", "h", "", set(""), set(""));
+const LIAISON: Rule = rule("liaison", "*", "", set(""), RuleOutput::Tail);
+const B_NASALIZATION: Rule = rule("b-nasalization", "b", "n", set("m"), RuleOutput::Keep);
+const N_H_LIAISON: Rule = rule("n-h-liaison", "n", "h", set(""), RuleOutput::Tail);
+const BS_LIAISON: Rule = rule("bs-liaison", "bs", "", set("p"), set("s"));
+const BS_SIMPLIFICATION: Rule = rule("bs-simplification", "bs", "*", set("p"), RuleOutput::Keep);
+const M_H_LIAISON: Rule = rule("m-h-liaison", "m", "h", set(""), RuleOutput::Tail);
+const NG_H_WEAKENING: Rule = rule("ng-h-weakening", "ng", "h", RuleOutput::Keep, set(""));
+const R_H_LIAISON: Rule = rule("r-h-liaison", "r", "h", set(""), RuleOutput::Tail);
+
+/// The rules [`KoreanSentence::applied`](crate::hangul::KoreanSentence::applied) uses by default.
+pub static STANDARD: [Rule; 6] = [
+    H_DELETION, LIAISON, B_NASALIZATION, N_H_LIAISON, BS_LIAISON, BS_SIMPLIFICATION, ];
+
+/// The standard rules plus ㅎ weakening after sonorants in casual speech
+/// (결혼 → 겨론, 강화 → 강와).
+pub static COLLOQUIAL: [Rule; 9] = [
+    H_DELETION, LIAISON, B_NASALIZATION, N_H_LIAISON, BS_LIAISON, BS_SIMPLIFICATION,
+    M_H_LIAISON, NG_H_WEAKENING, R_H_LIAISON, ];
+
+/// A rewrite of whole syllables at the end of a word, for reductions that
+/// are not a change between a tail and the next lead, or at its start, for
+/// word-initial tensing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reduction {
+    pub from: Cow<'static, str>,
+    pub to: Cow<'static, str>,
+}
+
+const fn reduction(from: &'static str, to: &'static str) -> Reduction {
+    Reduction { from: Cow::Borrowed(from), to: Cow::Borrowed(to) }
+}
+
+/// Casual contractions of 것 and its particles (그것을 → 그걸, 이것 → 이거).
+pub static COLLOQUIAL_REDUCTIONS: [Reduction; 4] = [
+    reduction("것을", "걸"), reduction("것은", "건"), reduction("것이", "게"), reduction("것", "거"), ];
+
+/// A change of one jamo wherever it occurs, for dialect sound shifts. Jamo
+/// are written in the crate's romanization, as in [`Rule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Shift {
+    /// Replaces the first lead with the second.
+    Lead(Cow<'static, str>, Cow<'static, str>),
+    /// Replaces the first vowel with the second.
+    Vowel(Cow<'static, str>, Cow<'static, str>),
+}
+
+const fn lead_shift(from: &'static str, to: &'static str) -> Shift {
+    Shift::Lead(Cow::Borrowed(from), Cow::Borrowed(to))
+}
+
+const fn vowel_shift(from: &'static str, to: &'static str) -> Shift {
+    Shift::Vowel(Cow::Borrowed(from), Cow::Borrowed(to))
+}
+
+/// ㅆ merging into ㅅ (쌀 → 살), ㅢ into ㅣ and ㅐ into ㅔ.
+pub static GYEONGSANG_SHIFTS: [Shift; 3] = [
+    lead_shift("ss", "s"), vowel_shift("eui", "i"), vowel_shift("ae", "e"), ];
+
+/// Word-initial tensing of Gyeongsang speech in words that commonly take it
+/// (작다 → 짝다, 고추 → 꼬추), matched at the start of a word. Onsets in ㅅ
+/// are left out, as ㅆ merges into ㅅ there.
+pub static GYEONGSANG_TENSING: [Reduction; 10] = [
+    reduction("작다", "짝다"), reduction("작은", "짝은"), reduction("작아", "짝아"),
+    reduction("좁", "쫍"), reduction("닦", "딲"), reduction("볶", "뽂"), reduction("고추", "꼬추"),
+    reduction("조금", "쪼금"), reduction("두껍", "뚜껍"), reduction("부수", "뿌수"), ];
+
+/// ㅢ becoming ㅡ (의사 → 으사), ㅚ and ㅐ becoming ㅔ, and ㅟ becoming ㅣ.
+pub static JEOLLA_SHIFTS: [Shift; 4] = [
+    vowel_shift("eui", "eu"), vowel_shift("oe", "e"), vowel_shift("ae", "e"), vowel_shift("wi", "i"), ];
+
+/// A curated, versioned set of rules and reductions.
+///
+/// The rules of a profile only change along with its [`version`](Self::version),
+/// so output can be pinned. To extend one, start from its statics:
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// use jamo::rules::{self, parse_rules, RuleProfile};
+/// assert_eq!("igeo joaseo", KoreanSentence::new("이것 좋아서").with_profile(RuleProfile::Colloquial).applied().roman());
+///
+/// let rules = [&rules::STANDARD[..], &parse_rules("tail=g lead=n => tail=ng").unwrap()].concat();
+/// assert_eq!("hangnyeon", KoreanSentence::new("학년").with_rules(rules).applied().roman());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RuleProfile {
+    /// 표준 발음법: [`STANDARD`] and no reductions.
+    Standard,
+    /// Casual speech: [`COLLOQUIAL`] and [`COLLOQUIAL_REDUCTIONS`].
+    Colloquial,
+    /// An approximation of Gyeongsang speech: [`STANDARD`],
+    /// [`GYEONGSANG_SHIFTS`] and the word-initial [`GYEONGSANG_TENSING`].
+    Gyeongsang,
+    /// An approximation of Jeolla speech: [`COLLOQUIAL`] and [`JEOLLA_SHIFTS`].
+    Jeolla,
+}
+
+impl RuleProfile {
+    pub const ALL: [RuleProfile; 4] = [
+        RuleProfile::Standard, RuleProfile::Colloquial, RuleProfile::Gyeongsang, RuleProfile::Jeolla, ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|p| p.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuleProfile::Standard => "standard",
+            RuleProfile::Colloquial => "colloquial",
+            RuleProfile::Gyeongsang => "gyeongsang",
+            RuleProfile::Jeolla => "jeolla",
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        match self {
+            RuleProfile::Standard => 1,
+            RuleProfile::Colloquial => 1,
+            RuleProfile::Gyeongsang => 3,
+            RuleProfile::Jeolla => 1,
+        }
+    }
+
+    pub fn rules(&self) -> &'static [Rule] {
+        match self {
+            RuleProfile::Standard | RuleProfile::Gyeongsang => &STANDARD,
+            RuleProfile::Colloquial | RuleProfile::Jeolla => &COLLOQUIAL,
+        }
+    }
+
+    pub fn reductions(&self) -> &'static [Reduction] {
+        match self {
+            RuleProfile::Colloquial => &COLLOQUIAL_REDUCTIONS,
+            _ => &[],
+        }
+    }
+
+    /// Returns the word-initial tensing of the profile, applied after its
+    /// shifts with [`KoreanSentence::rewrite_word_starts`].
+    ///
+    /// [`KoreanSentence::rewrite_word_starts`]: crate::hangul::KoreanSentence::rewrite_word_starts
+    pub fn tensing(&self) -> &'static [Reduction] {
+        match self {
+            RuleProfile::Gyeongsang => &GYEONGSANG_TENSING,
+            _ => &[],
+        }
+    }
+
+    /// Returns the dialect shifts of the profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::hangul::KoreanSentence;
+    /// use jamo::rules::RuleProfile;
+    /// let roman = |text, profile| KoreanSentence::new(text).with_profile(profile).applied().roman();
+    /// assert_eq!("sar isa sesang", roman("쌀 의사 세상", RuleProfile::Gyeongsang));
+    /// assert_eq!("tchogeum kkochu", roman("조금 고추", RuleProfile::Gyeongsang));
+    /// assert_eq!("eusa hesa gyeoron", roman("의사 회사 결혼", RuleProfile::Jeolla));
+    /// ```
+    pub fn shifts(&self) -> &'static [Shift] {
+        match self {
+            RuleProfile::Gyeongsang => &GYEONGSANG_SHIFTS,
+            RuleProfile::Jeolla => &JEOLLA_SHIFTS,
+            _ => &[],
+        }
+    }
+}
+
+/// Returns the rules of the built-in profile called `name`, one of the
+/// [`RuleProfile`] names or "none". Only the rules: use
+/// [`RuleProfile::from_name`] to get the reductions and shifts too.
+pub fn profile(name: &str) -> Option<&'static [Rule]> {
+    match name {
+        "none" => Some(&[]),
+        name => RuleProfile::from_name(name).map(|p| p.rules()),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// One-based line of the error.
+    pub line: usize,
+    /// One-based column of the error, in chars.
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses rules written one per line as `tail=h lead=* => tail= lead=`,
+/// with an optional `# name` at the end.
+///
+/// A pattern left out matches anything, as does `*`. An output left out keeps
+/// the jamo, an empty one deletes it, and `$tail` or `$lead` puts the old
+/// tail or lead there. Patterns and outputs must name jamo in the crate's
+/// romanization.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// use jamo::rules::parse_rules;
+/// let rules = parse_rules("tail=h lead= => tail= lead= # h-deletion\ntail=g lead=n => tail=ng").unwrap();
+/// assert_eq!("h-deletion", rules[0].name);
+/// assert_eq!("hangnyeon", KoreanSentence::new("학년").with_rules(rules).applied().roman());
+///
+/// let error = parse_rules("tail=h lead=x => tail=").unwrap_err();
+/// assert_eq!((1, 13), (error.line, error.column));
+///
+/// // Columns count chars, whatever the width of the whitespace before them.
+/// let error = parse_rules("\u{3000}\u{3000}foo=x => tail=").unwrap_err();
+/// assert_eq!((1, 3), (error.line, error.column));
+/// ```
+pub fn parse_rules(s: &str) -> Result<Vec<Rule>, ParseError> {
+    let mut rules = vec![];
+    for (i, line) in s.lines().enumerate() {
+        let (body, name) = match line.find('#') {
+            Some(at) => (&line[..at], Some(line[at + 1..].trim())),
+            None => (line, None),
+        };
+        if body.trim().is_empty() {
+            continue;
+        }
+        let arrow = body.find("=>").ok_or_else(|| error(i, line, body.len(), "expected =>"))?;
+        let mut rule = rule("", "*", "*", RuleOutput::Keep, RuleOutput::Keep);
+        for (output, part, offset) in [(false, &body[..arrow], 0), (true, &body[arrow + 2..], arrow + 2)] {
+            for (at, token) in tokens(part) {
+                let at = offset + at;
+                let (key, value) = token.split_once('=')
+                    .ok_or_else(|| error(i, line, at, "expected tail= or lead="))?;
+                let value_at = at + key.len() + 1;
+                let position = Position::new(key).ok_or_else(|| error(i, line, at, "expected tail= or lead="))?;
+                if output {
+                    let output = parse_output(position, value).map_err(|m| error(i, line, value_at, &m))?;
+                    match position {
+                        Position::Tail => rule.new_tail = output,
+                        Position::Lead => rule.new_lead = output,
+                    }
+                } else {
+                    let pattern = parse_pattern(position, value).map_err(|m| error(i, line, value_at, &m))?;
+                    match position {
+                        Position::Tail => rule.tail = pattern,
+                        Position::Lead => rule.lead = pattern,
+                    }
+                }
+            }
+        }
+        rule.name = match name {
+            Some(name) if !name.is_empty() => Cow::Owned(name.to_string()),
+            _ => Cow::Owned(format!("{}+{}", rule.tail, rule.lead)),
+        };
+        rules.push(rule);
+    }
+    Ok(rules)
+}
+
+/// Parses rules from `[[rule]]` tables with string keys `name`, `tail`,
+/// `lead`, `new_tail` and `new_lead`, following the same conventions as
+/// [`parse_rules`].
+///
+/// This is the small subset of TOML such a file needs, not a TOML parser:
+/// every line is blank, a `#` comment, `[[rule]]` or `key = "value"`, and
+/// values are basic strings without escapes. Anything else, including an
+/// inline comment after a value, is an error rather than misread.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::hangul::KoreanSentence;
+/// use jamo::rules::parse_rule_tables;
+/// let rules = parse_rule_tables(r#"
+/// ## ㄱ before ㄴ becomes ㅇ
+/// [[rule]]
+/// name = "g-nasalization"
+/// tail = "g"
+/// lead = "n"
+/// new_tail = "ng"
+/// "#).unwrap();
+/// assert_eq!("hangnyeon", KoreanSentence::new("학년").with_rules(rules).applied().roman());
+///
+/// let error = parse_rule_tables("[[rule]]\ntail = \"h\" # ㅎ").unwrap_err();
+/// assert_eq!((2, 8), (error.line, error.column));
+/// ```
+pub fn parse_rule_tables(s: &str) -> Result<Vec<Rule>, ParseError> {
+    let mut rules = vec![];
+    for (i, line) in s.lines().enumerate() {
+        let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed == "[[rule]]" {
+            rules.push(rule("", "*", "*", RuleOutput::Keep, RuleOutput::Keep));
+            continue;
+        }
+        let current = rules.last_mut().ok_or_else(|| error(i, line, indent, "expected [[rule]]"))?;
+        let (key, value) = trimmed.split_once('=')
+            .ok_or_else(|| error(i, line, indent, "expected key = \"value\""))?;
+        let value_at = indent + key.len() + 1 + (value.len() - value.trim_start().len());
+        let value = value.trim().strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+            .filter(|v| !v.contains(['"', '\\']))
+            .ok_or_else(|| error(i, line, value_at, "expected a quoted string without escapes"))?;
+        let value_at = value_at + 1;
+        let at_value = |m: String| error(i, line, value_at, &m);
+        match key.trim() {
+            "name" => current.name = Cow::Owned(value.to_string()),
+            "tail" => current.tail = parse_pattern(Position::Tail, value).map_err(at_value)?,
+            "lead" => current.lead = parse_pattern(Position::Lead, value).map_err(at_value)?,
+            "new_tail" => current.new_tail = parse_output(Position::Tail, value).map_err(at_value)?,
+            "new_lead" => current.new_lead = parse_output(Position::Lead, value).map_err(at_value)?,
+            key => return Err(error(i, line, indent, &format!("unknown key {}", key))),
+        }
+    }
+    Ok(rules)
+}
+
+/// Loads rules in the table format of [`parse_rule_tables`].
+pub fn load_rule_tables<P: AsRef<Path>>(path: P) -> io::Result<Vec<Rule>> {
+    parse_rule_tables(&fs::read_to_string(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Loads rules in the line format of [`parse_rules`].
+pub fn load_rules<P: AsRef<Path>>(path: P) -> io::Result<Vec<Rule>> {
+    parse_rules(&fs::read_to_string(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[derive(Clone, Copy)]
+enum Position {
+    Tail,
+    Lead,
+}
+
+impl Position {
+    fn new(key: &str) -> Option<Self> {
+        match key {
+            "tail" => Some(Position::Tail),
+            "lead" => Some(Position::Lead),
+            _ => None,
+        }
+    }
+
+    fn is_jamo(&self, s: &str) -> bool {
+        match self {
+            Position::Tail => is_tail_pattern(s) && s != "*",
+            Position::Lead => is_lead_pattern(s) && s != "*",
+        }
+    }
+}
+
+fn parse_pattern(position: Position, value: &str) -> Result<Cow<'static, str>, String> {
+    if value == "*" || position.is_jamo(value) {
+        Ok(Cow::Owned(value.to_string()))
+    } else {
+        Err(format!("unknown jamo {:?}", value))
+    }
+}
+
+fn parse_output(position: Position, value: &str) -> Result<RuleOutput, String> {
+    match value {
+        "$tail" => Ok(RuleOutput::Tail),
+        "$lead" => Ok(RuleOutput::Lead),
+        _ if position.is_jamo(value) => Ok(RuleOutput::Set(Cow::Owned(value.to_string()))),
+        _ => Err(format!("unknown jamo {:?}", value)),
+    }
+}
+
+/// Returns the whitespace-separated tokens of `s` with their byte offsets.
+fn tokens(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.split(char::is_whitespace)
+        .filter(|token| !token.is_empty())
+        .map(move |token| (token.as_ptr() as usize - s.as_ptr() as usize, token))
+}
+
+fn error(line_index: usize, line: &str, byte: usize, message: &str) -> ParseError {
+    ParseError {
+        line: line_index + 1,
+        column: line[..byte.min(line.len())].chars().count() + 1,
+        message: message.to_string(),
+    }
+}
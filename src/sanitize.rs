@@ -0,0 +1,114 @@
+//! Detection and removal of invisible characters hidden in text: zero-width
+//! spaces and joiners, bidirectional controls and variation selectors. Pasted
+//! or adversarial text carries them between syllables, where they split
+//! words, block the rules and defeat matching without showing on screen.
+
+use crate::blocks;
+use crate::hangul::Origin;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvisibleKind {
+    /// Zero-width space, joiner and non-joiner, word joiner, byte order
+    /// mark and soft hyphen.
+    ZeroWidth,
+    /// Marks, embeddings, overrides and isolates that change the direction
+    /// text is displayed in.
+    BidiControl,
+    /// Selectors choosing a glyph variant of the preceding character.
+    VariationSelector,
+}
+
+/// An invisible character, as reported by [`find_invisible`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invisible {
+    pub origin: Origin,
+    pub kind: InvisibleKind,
+    /// Whether a Hangul syllable or jamo is the nearest visible character on
+    /// either side.
+    pub in_hangul: bool,
+}
+
+/// Returns the kind of invisible character `c` is, if it is one.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::sanitize::{invisible_kind, InvisibleKind};
+/// assert_eq!(Some(InvisibleKind::ZeroWidth), invisible_kind('\u{200b}'));
+/// assert_eq!(Some(InvisibleKind::BidiControl), invisible_kind('\u{202e}'));
+/// assert_eq!(None, invisible_kind(' '));
+/// ```
+pub fn invisible_kind(c: char) -> Option<InvisibleKind> {
+    match c {
+        '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' | '\u{00ad}' | '\u{180e}' => Some(InvisibleKind::ZeroWidth),
+        '\u{200e}' | '\u{200f}' | '\u{061c}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}' => {
+            Some(InvisibleKind::BidiControl)
+        }
+        '\u{fe00}'..='\u{fe0f}' | '\u{e0100}'..='\u{e01ef}' => Some(InvisibleKind::VariationSelector),
+        _ => None,
+    }
+}
+
+/// Returns every invisible character in `text`, in order.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::sanitize::{find_invisible, InvisibleKind};
+/// let found = find_invisible("안\u{200b}녕 👍\u{fe0f}");
+/// assert_eq!(2, found.len());
+/// assert_eq!(3..6, found[0].origin.byte_range);
+/// assert!(found[0].in_hangul);
+/// assert_eq!(InvisibleKind::VariationSelector, found[1].kind);
+/// assert!(!found[1].in_hangul);
+///
+/// let run = format!("가{}나", "\u{200b}".repeat(100_000));
+/// assert!(find_invisible(&run).iter().all(|i| i.in_hangul));
+/// ```
+pub fn find_invisible(text: &str) -> Vec<Invisible> {
+    let chars = text.char_indices().collect::<Vec<(usize, char)>>();
+    // Whether the nearest visible char after each char is Hangul, found in
+    // one backward pass so runs of invisible chars stay linear.
+    let mut hangul_after = vec![false; chars.len()];
+    let mut next_hangul = false;
+    for (i, &(_, c)) in chars.iter().enumerate().rev() {
+        hangul_after[i] = next_hangul;
+        if invisible_kind(c).is_none() {
+            next_hangul = blocks::is_hangul(c);
+        }
+    }
+    let mut found = vec![];
+    let mut previous_hangul = false;
+    for (char_index, &(byte, c)) in chars.iter().enumerate() {
+        let Some(kind) = invisible_kind(c) else {
+            previous_hangul = blocks::is_hangul(c);
+            continue;
+        };
+        let in_hangul = previous_hangul || hangul_after[char_index];
+        let origin = Origin { char_index, byte_range: byte..byte + c.len_utf8(), original: c };
+        found.push(Invisible { origin, kind, in_hangul });
+    }
+    found
+}
+
+/// Returns `text` without invisible characters, keeping only zero-width
+/// joiners and variation selectors outside Hangul, which emoji need.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::sanitize::sanitize;
+/// assert_eq!("안녕하세요", sanitize("안\u{200b}녕\u{202e}하\u{fe0f}세\u{200d}요"));
+/// assert_eq!("❤\u{fe0f}", sanitize("❤\u{fe0f}"));
+/// ```
+pub fn sanitize(text: &str) -> String {
+    let stripped = find_invisible(text).into_iter().filter(is_stripped).map(|i| i.origin.char_index).collect::<Vec<usize>>();
+    text.chars().enumerate().filter(|(i, _)| stripped.binary_search(i).is_err()).map(|(_, c)| c).collect()
+}
+
+/// Returns whether [`sanitize`] removes `invisible`.
+pub(crate) fn is_stripped(invisible: &Invisible) -> bool {
+    invisible.in_hangul
+        || invisible.kind == InvisibleKind::BidiControl
+        || invisible.kind == InvisibleKind::ZeroWidth && invisible.origin.original != '\u{200d}'
+}
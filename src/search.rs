@@ -0,0 +1,254 @@
+use std::ops::Range;
+
+use crate::blocks::{self, Block};
+use crate::hangul::{KoreanSentence, Letter};
+
+/// Returns a normalized key for fuzzy search indexes.
+///
+/// Hangul is rule-applied and romanized, everything is lowercased, and runs of
+/// anything but letters and digits become a single space. An `h` between a
+/// vowel or sonorant and a following vowel is dropped on both sides, mirroring
+/// ㅎ-weakening, so spelled, pronounced and romanized forms of a word collide.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::search::search_key;
+/// assert_eq!("joayo", search_key("좋아요"));
+/// assert_eq!("joayo", search_key("조아요."));
+/// assert_eq!("joayo", search_key("Johayo"));
+/// ```
+pub fn search_key(text: &str) -> String {
+    let roman = KoreanSentence::new(text).applied().roman().to_lowercase();
+    let words = roman
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let chars = words.chars().collect::<Vec<char>>();
+    chars.iter().enumerate().filter(|&(i, &c)| {
+        let weakened = c == 'h'
+            && i > 0 && "aeiounmlr".contains(chars[i - 1])
+            && chars.get(i + 1).is_some_and(|&n| "aeiouwy".contains(n));
+        !weakened
+    }).map(|(_, &c)| c).collect()
+}
+
+// One code per jamo index. Tense consonants share their plain counterpart's
+// code, ㅐ/ㅔ, ㅒ/ㅖ and ㅙ/ㅞ/ㅚ are merged, and tails are neutralized to
+// the seven pronounced codas (plus _ for none).
+const LEAD_CODES: &str = "GGNDDRMBBSS0JJCKTPH";
+const VOWEL_CODES: &str = "aeyxveqxowffzumfkjghi";
+const TAIL_CODES: &str = "_KKKNNNTLKMLLLPLMPPTTQTTKTPT";
+
+/// Returns a code that stays the same across common spelling variations of
+/// the same pronunciation, for deduplicating user-generated spellings.
+///
+/// Each syllable of the rule-applied word contributes three characters;
+/// letters and digits outside Hangul are kept lowercased and everything else
+/// is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::search::phonetic_hash;
+/// assert_eq!(phonetic_hash("짜장면"), phonetic_hash("자장면"));
+/// assert_eq!(phonetic_hash("게"), phonetic_hash("개"));
+/// assert_eq!(phonetic_hash("꽃"), phonetic_hash("꼳"));
+/// assert_ne!(phonetic_hash("달"), phonetic_hash("탈"));
+/// ```
+pub fn phonetic_hash(word: &str) -> String {
+    let mut hash = String::new();
+    for letter in KoreanSentence::new(word).applied().letters() {
+        match letter {
+            Letter::HangulLetter(h) => {
+                hash.push(LEAD_CODES.as_bytes()[h.lead().index()] as char);
+                hash.push(VOWEL_CODES.as_bytes()[h.vowel().index()] as char);
+                hash.push(TAIL_CODES.as_bytes()[h.tail().index()] as char);
+            }
+            Letter::OtherLetter(c) if c.is_alphanumeric() => hash.extend(c.to_lowercase()),
+            Letter::OtherLetter(_) => {}
+        }
+    }
+    hash
+}
+
+/// Returns whether `a` and `b` rhyme: whether the vowels and tails of their
+/// last `depth` pronounced syllables sound alike, at least one syllable.
+///
+/// Words are compared after the rules, with ㅐ/ㅔ merged and tails
+/// neutralized as in [`phonetic_hash`], so rhymes follow sound, not spelling.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::search::rhymes_with;
+/// assert!(!rhymes_with("사랑", "바람", 1));
+/// assert!(rhymes_with("사랑", "가방", 1));
+/// assert!(rhymes_with("꽃", "옷", 1));
+/// assert!(rhymes_with("사랑해", "다방에", 2));
+/// assert!(!rhymes_with("사랑해", "바다에", 2));
+/// ```
+pub fn rhymes_with(a: &str, b: &str, depth: usize) -> bool {
+    let (a, b) = (rhyme_codes(a), rhyme_codes(b));
+    let depth = depth.max(1);
+    a.len() >= depth && b.len() >= depth && a[a.len() - depth..] == b[b.len() - depth..]
+}
+
+fn rhyme_codes(word: &str) -> Vec<(u8, u8)> {
+    KoreanSentence::new(word).applied().letters().iter().filter_map(|letter| match letter {
+        Letter::HangulLetter(h) => {
+            Some((VOWEL_CODES.as_bytes()[h.vowel().index()], TAIL_CODES.as_bytes()[h.tail().index()]))
+        }
+        Letter::OtherLetter(_) => None,
+    }).collect()
+}
+
+// Jamo folded together when matching keywords: tense and aspirated
+// consonants become plain, ㅐ/ㅔ and ㅒ/ㅖ merge. Characters that pass for
+// jamo (1 for ㅣ, 7 for ㄱ) are read as them.
+const KEYWORD_FOLDS: [(char, char); 19] = [
+    ('ㄲ', 'ㄱ'), ('ㅋ', 'ㄱ'), ('ㄸ', 'ㄷ'), ('ㅌ', 'ㄷ'), ('ㅃ', 'ㅂ'), ('ㅍ', 'ㅂ'), ('ㅆ', 'ㅅ'),
+    ('ㅉ', 'ㅈ'), ('ㅊ', 'ㅈ'), ('ㅐ', 'ㅔ'), ('ㅒ', 'ㅖ'),
+    ('1', 'ㅣ'), ('l', 'ㅣ'), ('I', 'ㅣ'), ('|', 'ㅣ'), ('!', 'ㅣ'), ('7', 'ㄱ'),
+    ('一', 'ㅡ'), ('0', 'ㅇ'), ];
+
+/// A keyword found by a [`KeywordMatcher`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeywordMatch {
+    /// The index of the keyword, in the order given to the matcher.
+    pub keyword: usize,
+    /// The byte range of the text the keyword was found in.
+    pub range: Range<usize>,
+}
+
+/// Finds keywords in text even when they are disguised: spaced or
+/// punctuated apart, typed as separate jamo, respelled with tense or
+/// aspirated consonants, or with the jamo regrouped into other syllables.
+///
+/// Text and keywords are both reduced to folded jamo keystrokes, with silent
+/// ㅇ leads, spaces and punctuation dropped, and matched on those. By default
+/// a keyword is found anywhere, even inside a longer word or across two;
+/// [`whole_words`](Self::whole_words) and [`allow`](Self::allow) narrow that.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::search::KeywordMatcher;
+/// let matcher = KeywordMatcher::new(&["시발", "바보"]);
+/// for text in ["시발", "씨발", "ㅅ1발", "시 . 발", "시-발", "ㅅㅣㅂㅏㄹ", "십알", "씨빨"] {
+///     assert!(matcher.is_match(text), "{}", text);
+/// }
+/// assert!(!matcher.is_match("사발"));
+/// assert!(matcher.is_match("다시 발견"));
+/// let found = matcher.find_all("너 ㅂ ㅏ 보 야");
+/// assert_eq!(1, found[0].keyword);
+/// assert_eq!(4..15, found[0].range);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeywordMatcher {
+    keywords: Vec<Vec<char>>,
+    whole_words: bool,
+    allowed: Vec<String>,
+}
+
+impl KeywordMatcher {
+    pub fn new<S: AsRef<str>>(keywords: &[S]) -> Self {
+        let keywords = keywords.iter()
+            .map(|k| keyword_key(k.as_ref()).into_iter().map(|(c, _)| c).collect())
+            .collect();
+        KeywordMatcher { keywords, whole_words: false, allowed: vec![] }
+    }
+
+    /// Only finds keywords that start and end at word boundaries in the text,
+    /// however they are spaced or punctuated in between.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::search::KeywordMatcher;
+    /// let matcher = KeywordMatcher::new(&["시발"]).whole_words(true);
+    /// assert!(matcher.is_match("야 시 . 발!"));
+    /// assert!(!matcher.is_match("다시 발견"));
+    /// assert!(!matcher.is_match("시발점"));
+    /// ```
+    pub fn whole_words(mut self, whole_words: bool) -> Self {
+        self.whole_words = whole_words;
+        self
+    }
+
+    /// Ignores keywords found inside an occurrence of one of `words` in the
+    /// text, for innocent words that contain a keyword.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::search::KeywordMatcher;
+    /// let matcher = KeywordMatcher::new(&["시발"]).allow(&["시발점", "다시 발견"]);
+    /// assert!(!matcher.is_match("여기가 시발점이다. 다시 발견했다"));
+    /// assert!(matcher.is_match("시발점 말고 시발"));
+    /// ```
+    pub fn allow<S: AsRef<str>>(mut self, words: &[S]) -> Self {
+        self.allowed.extend(words.iter().map(|w| w.as_ref().to_string()));
+        self
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        !self.find_all(text).is_empty()
+    }
+
+    /// Returns every occurrence of every keyword in `text`, ordered by start.
+    pub fn find_all(&self, text: &str) -> Vec<KeywordMatch> {
+        let key = keyword_key(text);
+        let mut matches = vec![];
+        for (keyword, pattern) in self.keywords.iter().enumerate() {
+            if pattern.is_empty() || pattern.len() > key.len() {
+                continue;
+            }
+            for start in 0..=key.len() - pattern.len() {
+                let window = &key[start..start + pattern.len()];
+                if window.iter().zip(pattern).all(|((c, _), p)| c == p) {
+                    let range = window[0].1.start..window[window.len() - 1].1.end;
+                    matches.push(KeywordMatch { keyword, range });
+                }
+            }
+        }
+        let allowed = self.allowed.iter().filter(|w| !w.is_empty())
+            .flat_map(|w| text.match_indices(w.as_str()).map(|(i, w)| i..i + w.len()))
+            .collect::<Vec<Range<usize>>>();
+        let word_char = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+        matches.retain(|m| {
+            let bounded = !word_char(text[..m.range.start].chars().next_back()) && !word_char(text[m.range.end..].chars().next());
+            (!self.whole_words || bounded) && !allowed.iter().any(|a| a.start <= m.range.start && m.range.end <= a.end)
+        });
+        matches.sort_by_key(|m| (m.range.start, m.keyword));
+        matches
+    }
+}
+
+/// Returns the folded jamo keystrokes of `text` with the byte range of the
+/// character each came from.
+fn keyword_key(text: &str) -> Vec<(char, Range<usize>)> {
+    let mut key = vec![];
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let range = i..i + c.len_utf8();
+        let c = blocks::to_compatibility(c).unwrap_or(c);
+        let strokes = match Letter::new(c) {
+            Letter::HangulLetter(h) if h.lead().index() == 11 => format!("{}{}", h.vowel().strokes(), h.tail().strokes()),
+            letter => letter.strokes(),
+        };
+        for stroke in strokes.chars() {
+            let folded = KEYWORD_FOLDS.iter().find(|&&(from, _)| from == stroke).map_or(stroke, |&(_, to)| to);
+            let vowel_next = chars.peek().and_then(|&(_, n)| blocks::to_compatibility(n))
+                .is_some_and(|n| blocks::index(n, blocks::Position::Vowel).is_some());
+            if folded == 'ㅇ' && strokes.chars().count() == 1 && vowel_next {
+                continue;
+            }
+            if folded.is_alphanumeric() || blocks::block(folded) == Some(Block::Compatibility) {
+                key.push((folded, range.clone()));
+            }
+        }
+    }
+    key
+}
@@ -0,0 +1,127 @@
+//! Detection of spoofing tricks in Korean identifiers such as usernames and
+//! domain labels: Latin or Cyrillic letters standing in for jamo, Hangul
+//! Fillers used as blank characters, and invisible characters.
+
+use crate::blocks;
+use crate::hangul::Origin;
+use crate::sanitize::{find_invisible, invisible_kind, InvisibleKind};
+
+// Latin and Cyrillic letters that pass for a compatibility jamo.
+const LOOKALIKES: [(char, char); 17] = [
+    ('o', 'ㅇ'), ('O', 'ㅇ'), ('о', 'ㅇ'), ('О', 'ㅇ'),
+    ('l', 'ㅣ'), ('I', 'ㅣ'), ('і', 'ㅣ'), ('І', 'ㅣ'), ('ӏ', 'ㅣ'),
+    ('L', 'ㄴ'), ('T', 'ㅜ'), ('Т', 'ㅜ'), ('т', 'ㅜ'),
+    ('E', 'ㅌ'), ('Е', 'ㅌ'), ('П', 'ㅠ'), ('ʌ', 'ㅅ'), ];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Confusion {
+    /// A Latin or Cyrillic letter imitating the contained jamo.
+    Lookalike(char),
+    /// A Cyrillic letter in a word with Hangul.
+    MixedScript,
+    /// A Hangul Filler outside a conjoining jamo sequence, which renders as
+    /// blank space.
+    HangulFiller,
+    /// A character that does not render at all, see [`crate::sanitize`].
+    Invisible(InvisibleKind),
+}
+
+/// A suspicious character, as reported by [`detect_confusables`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Confusable {
+    pub origin: Origin,
+    pub confusion: Confusion,
+}
+
+/// Returns the characters of `text` that make it look like other Korean
+/// text, in order.
+///
+/// Invisible characters are reported and otherwise skipped, so they cannot
+/// hide a lookalike from its neighbors. A run of Latin letters next to
+/// Hangul is reported when every letter in it passes for a jamo, so "관ll자"
+/// and "운영Io" are caught while "LG전자" passes. Any Cyrillic letter in a
+/// word with Hangul is reported. Hangul Fillers are reported unless they
+/// stand in for the lead or vowel of a conjoining jamo sequence.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::security::{detect_confusables, Confusion};
+/// let found = detect_confusables("관l자");
+/// assert_eq!(Confusion::Lookalike('ㅣ'), found[0].confusion);
+/// assert_eq!(3..4, found[0].origin.byte_range);
+/// assert_eq!(2, detect_confusables("관ll자").len());
+/// assert_eq!(2, detect_confusables("운영Io").len());
+/// assert_eq!(2, detect_confusables("관\u{200b}l자").len());
+///
+/// // Cyrillic о
+/// assert_eq!(Confusion::Lookalike('ㅇ'), detect_confusables("ㅎоㅎ")[0].confusion);
+/// assert_eq!(Confusion::HangulFiller, detect_confusables("운영자\u{3164}")[0].confusion);
+/// assert!(detect_confusables("LG전자 서비스").is_empty());
+/// ```
+pub fn detect_confusables(text: &str) -> Vec<Confusable> {
+    let mut found = find_invisible(text).into_iter()
+        .map(|i| Confusable { origin: i.origin, confusion: Confusion::Invisible(i.kind) })
+        .collect::<Vec<Confusable>>();
+    let visible = text.char_indices().enumerate()
+        .filter(|(_, (_, c))| invisible_kind(*c).is_none())
+        .map(|(char_index, (byte, c))| (char_index, byte, c))
+        .collect::<Vec<(usize, usize, char)>>();
+    for word in visible.split(|&(_, _, c)| c.is_whitespace()) {
+        let has_hangul = word.iter().any(|&(_, _, c)| blocks::is_hangul(c));
+        let mut i = 0;
+        while i < word.len() {
+            let (char_index, byte, c) = word[i];
+            let before = i.checked_sub(1).map(|i| word[i].2);
+            if !is_latin(c) && !is_cyrillic(c) {
+                if blocks::is_filler(c) {
+                    let after = word.get(i + 1).map(|&(_, _, c)| c);
+                    let conjoined = match c {
+                        '\u{115f}' => after.is_some_and(|a| ('\u{1160}'..='\u{11a7}').contains(&a)),
+                        '\u{1160}' => before.is_some_and(|b| ('\u{1100}'..='\u{115f}').contains(&b)),
+                        _ => false,
+                    };
+                    if !conjoined {
+                        found.push(confusable(char_index, byte, c, Confusion::HangulFiller));
+                    }
+                }
+                i += 1;
+                continue;
+            }
+            let run = word[i..].iter().take_while(|&&(_, _, c)| is_latin(c) || is_cyrillic(c)).count();
+            let after = word.get(i + run).map(|&(_, _, c)| c);
+            let touches_hangul = before.into_iter().chain(after).any(blocks::is_hangul);
+            let latin_lookalikes = word[i..i + run].iter().filter(|&&(_, _, c)| is_latin(c)).all(|&(_, _, c)| lookalike(c).is_some());
+            for &(char_index, byte, c) in &word[i..i + run] {
+                let confusion = match lookalike(c) {
+                    _ if is_cyrillic(c) && !has_hangul => None,
+                    Some(jamo) if is_cyrillic(c) || touches_hangul && latin_lookalikes => Some(Confusion::Lookalike(jamo)),
+                    None if is_cyrillic(c) => Some(Confusion::MixedScript),
+                    _ => None,
+                };
+                if let Some(confusion) = confusion {
+                    found.push(confusable(char_index, byte, c, confusion));
+                }
+            }
+            i += run;
+        }
+    }
+    found.sort_by_key(|c| c.origin.char_index);
+    found
+}
+
+fn confusable(char_index: usize, byte: usize, c: char, confusion: Confusion) -> Confusable {
+    Confusable { origin: Origin { char_index, byte_range: byte..byte + c.len_utf8(), original: c }, confusion }
+}
+
+fn lookalike(c: char) -> Option<char> {
+    LOOKALIKES.iter().find(|&&(from, _)| from == c).map(|&(_, jamo)| jamo)
+}
+
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphabetic() || c.is_alphabetic() && ('\u{00c0}'..='\u{02af}').contains(&c)
+}
+
+fn is_cyrillic(c: char) -> bool {
+    ('\u{0400}'..='\u{04ff}').contains(&c)
+}
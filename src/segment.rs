@@ -0,0 +1,76 @@
+use crate::josa::split_particle;
+
+const ENDERS: [char; 7] = ['.', '?', '!', '…', '。', '？', '！'];
+const CLOSERS: [char; 9] = ['"', '\'', '”', '’', '」', '』', ')', '）', ']'];
+
+/// Splits `text` into sentences, each ending after its terminal punctuation
+/// and any closing quotes. Terminal punctuation only ends a sentence before
+/// whitespace or the end of the text, so "3.5" and "다...다" stay whole.
+///
+/// Applying rules per sentence keeps sandhi from reaching across sentences.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::segment::sentences;
+/// let text = "비가 왔다. 정말요?! 그는 \"그만해요.\" 하고 말했다… 3.5점";
+/// assert_eq!(
+///     vec!["비가 왔다.", "정말요?!", "그는 \"그만해요.\"", "하고 말했다…", "3.5점"],
+///     sentences(text));
+/// ```
+pub fn sentences(text: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if !ENDERS.contains(&c) {
+            continue;
+        }
+        while let Some(&(_, next)) = chars.peek() {
+            if !ENDERS.contains(&next) && !CLOSERS.contains(&next) {
+                break;
+            }
+            chars.next();
+        }
+        let end = chars.peek().map_or(text.len(), |&(i, _)| i);
+        if !chars.peek().is_some_and(|(_, next)| !next.is_whitespace()) {
+            push_trimmed(&mut result, &text[start..end]);
+            start = end;
+        }
+    }
+    push_trimmed(&mut result, &text[start..]);
+    result
+}
+
+fn push_trimmed<'a>(result: &mut Vec<&'a str>, s: &'a str) {
+    let s = s.trim();
+    if !s.is_empty() {
+        result.push(s);
+    }
+}
+
+/// Splits `text` on whitespace and peels trailing particles off each token,
+/// leaving the stems. Surrounding punctuation is dropped.
+///
+/// This is a heuristic, not a morphological analysis: a word whose last
+/// syllables happen to spell a particle loses them too.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::segment::words;
+/// assert_eq!(vec!["친구", "학교", "책", "읽었다"], words("친구는 학교에서 책을 읽었다."));
+/// assert_eq!(vec!["서울", "부산"], words("\"서울에서는\" 부산까지"));
+/// ```
+pub fn words(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .map(|token| token.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|token| !token.is_empty())
+        .map(|mut token| {
+            while let Some((stem, _)) = split_particle(token) {
+                token = stem;
+            }
+            token
+        })
+        .collect()
+}
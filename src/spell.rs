@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::blocks;
+use crate::hangul::{decompose, KoreanSentence, Letter};
+use crate::keyboard;
+
+pub struct Entry {
+    pub word: String,
+    pub frequency: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    pub distance: usize,
+    pub frequency: u64,
+}
+
+/// A suggestion from [`Dictionary::suggest_with`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedSuggestion {
+    pub suggestion: Suggestion,
+    /// The edit distance weighted by the confusion matrix used to rank the word.
+    pub cost: f64,
+}
+
+/// A word list indexed by jamo skeleton and by length in jamo, for
+/// spellchecking.
+#[derive(Default)]
+pub struct Dictionary {
+    entries: Vec<Entry>,
+    lookup: HashMap<String, usize>,
+    skeletons: HashMap<String, Vec<usize>>,
+    // The jamo of each entry, and the entries by their number of jamo.
+    streams: Vec<Vec<char>>,
+    lengths: Vec<Vec<usize>>,
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a dictionary from a word list with one word per line,
+    /// optionally followed by whitespace and a frequency count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::Dictionary;
+    /// let dict = Dictionary::from_word_list("있다 120\n잇다 3\n읽다 40\n");
+    /// assert!(dict.check("있다"));
+    /// assert!(!dict.check("있따"));
+    /// assert_eq!("있다", dict.suggest("있따", 1)[0].word);
+    /// ```
+    pub fn from_word_list(s: &str) -> Self {
+        let mut dict = Self::new();
+        for line in s.lines() {
+            let mut fields = line.split_whitespace();
+            if let Some(word) = fields.next() {
+                let frequency = fields.next().and_then(|f| f.parse().ok()).unwrap_or(1);
+                dict.insert(word, frequency);
+            }
+        }
+        dict
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::from_word_list(&fs::read_to_string(path)?))
+    }
+
+    /// Adds a word, accumulating its frequency if it is already known.
+    pub fn insert(&mut self, word: &str, frequency: u64) {
+        if let Some(&i) = self.lookup.get(word) {
+            self.entries[i].frequency += frequency;
+            return;
+        }
+        let i = self.entries.len();
+        self.entries.push(Entry { word: word.to_string(), frequency });
+        self.lookup.insert(word.to_string(), i);
+        self.skeletons.entry(skeleton(word)).or_default().push(i);
+        let stream = jamo_stream(word);
+        if self.lengths.len() <= stream.len() {
+            self.lengths.resize(stream.len() + 1, vec![]);
+        }
+        self.lengths[stream.len()].push(i);
+        self.streams.push(stream);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn check(&self, word: &str) -> bool {
+        self.lookup.contains_key(word)
+    }
+
+    pub fn frequency(&self, word: &str) -> Option<u64> {
+        self.lookup.get(word).map(|&i| self.entries[i].frequency)
+    }
+
+    /// Returns the words sharing the given lead-consonant skeleton (see [`skeleton`]).
+    pub fn words_with_skeleton(&self, skeleton: &str) -> Vec<&str> {
+        self.skeletons.get(skeleton).map_or(vec![], |indices| {
+            indices.iter().map(|&i| self.entries[i].word.as_str()).collect()
+        })
+    }
+
+    /// Returns the `n` closest words by jamo edit distance.
+    ///
+    /// Ties are broken by preferring words with the same skeleton as `word`,
+    /// then higher frequency.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::Dictionary;
+    /// let dict = Dictionary::from_word_list("가그 1\n각시 100\n");
+    /// let suggestions = dict.suggest("각", 2);
+    /// assert_eq!(("각시", 2), (suggestions[0].word.as_str(), suggestions[0].distance));
+    /// assert_eq!(("가그", 2), (suggestions[1].word.as_str(), suggestions[1].distance));
+    /// ```
+    pub fn suggest(&self, word: &str, n: usize) -> Vec<Suggestion> {
+        self.rank(word, n, |a, b| levenshtein(a, b) as f64).into_iter().map(|(_, i)| Suggestion {
+            word: self.entries[i].word.clone(),
+            distance: levenshtein(&jamo_stream(word), &self.streams[i]),
+            frequency: self.entries[i].frequency,
+        }).collect()
+    }
+
+    /// Returns the `n` closest words by jamo edit distance weighted by
+    /// `matrix`, so that likely slips rank above unlikely ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::{ConfusionMatrix, Dictionary};
+    /// let dict = Dictionary::from_word_list("사랑 10\n사망 10\n");
+    /// // ㅇ and ㄹ are on touching keys of the 2-set layout, ㅇ and ㅁ are not.
+    /// let suggestions = dict.suggest_with("사앙", 2, &ConfusionMatrix::dubeolsik());
+    /// assert_eq!("사랑", suggestions[0].suggestion.word);
+    /// assert!(suggestions[0].cost < suggestions[1].cost);
+    /// ```
+    pub fn suggest_with(&self, word: &str, n: usize, matrix: &ConfusionMatrix) -> Vec<WeightedSuggestion> {
+        self.rank(word, n, |a, b| weighted_levenshtein(a, b, matrix)).into_iter().map(|(cost, i)| WeightedSuggestion {
+            suggestion: Suggestion {
+                word: self.entries[i].word.clone(),
+                distance: levenshtein(&jamo_stream(word), &self.streams[i]),
+                frequency: self.entries[i].frequency,
+            },
+            cost,
+        }).collect()
+    }
+
+    /// Returns the `n` entries with the lowest `cost` from `word`, with their
+    /// cost, ties broken as in [`Dictionary::suggest`].
+    ///
+    /// Inserting or deleting a jamo costs 1, so no word differing from `word`
+    /// by more jamo than the `n`th best cost so far can make the cut: words
+    /// are tried in order of that difference, stopping there.
+    fn rank(&self, word: &str, n: usize, cost: impl Fn(&[char], &[char]) -> f64) -> Vec<(f64, usize)> {
+        let target = jamo_stream(word);
+        let same_skeleton = self.skeletons.get(&skeleton(word));
+        let mut best = Vec::<(f64, bool, usize)>::new();
+        for difference in 0..=target.len().max(self.lengths.len()) {
+            if n == 0 || best.len() >= n && difference as f64 > best[n - 1].0 {
+                break;
+            }
+            let shorter = target.len().checked_sub(difference);
+            let longer = Some(target.len() + difference).filter(|_| difference > 0);
+            for &i in shorter.into_iter().chain(longer).filter_map(|len| self.lengths.get(len)).flatten() {
+                let off_skeleton = !same_skeleton.is_some_and(|s| s.contains(&i));
+                best.push((cost(&target, &self.streams[i]), off_skeleton, i));
+            }
+            best.sort_by(|a, b| {
+                let (ea, eb) = (&self.entries[a.2], &self.entries[b.2]);
+                a.0.total_cmp(&b.0).then(a.1.cmp(&b.1))
+                    .then_with(|| eb.frequency.cmp(&ea.frequency))
+                    .then_with(|| ea.word.cmp(&eb.word))
+            });
+            best.truncate(n);
+        }
+        best.into_iter().map(|(cost, _, i)| (cost, i)).collect()
+    }
+}
+
+/// The cost of substituting one jamo for another when weighting edit
+/// distance. Pairs that are not listed cost 1, as do insertions and deletions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfusionMatrix {
+    costs: HashMap<(char, char), f64>,
+}
+
+// The cost of swapping jamo on touching keys, and on the same key with and
+// without shift.
+const ADJACENT_KEY_COST: f64 = 0.5;
+const SHIFT_SLIP_COST: f64 = 0.3;
+
+// Jamo that scanned text at low resolution mistakes for each other, with the
+// cost of swapping them: a stroke merged or lost (ㅜ/ㅡ, ㅣ/ㅏ) costs less
+// than a changed shape (ㅇ/ㅁ).
+const OCR_CONFUSIONS: [(char, char, f64); 26] = [
+    ('ㅜ', 'ㅡ', 0.3), ('ㅗ', 'ㅡ', 0.3), ('ㅏ', 'ㅣ', 0.3), ('ㅓ', 'ㅣ', 0.3),
+    ('ㅏ', 'ㅑ', 0.3), ('ㅓ', 'ㅕ', 0.3), ('ㅗ', 'ㅛ', 0.3), ('ㅜ', 'ㅠ', 0.3),
+    ('ㅐ', 'ㅔ', 0.3), ('ㅒ', 'ㅖ', 0.3), ('ㅐ', 'ㅒ', 0.5), ('ㅔ', 'ㅖ', 0.5),
+    ('ㅚ', 'ㅟ', 0.5), ('ㅢ', 'ㅚ', 0.6), ('ㅏ', 'ㅓ', 0.6),
+    ('ㄱ', 'ㅋ', 0.4), ('ㄷ', 'ㅌ', 0.4), ('ㅈ', 'ㅊ', 0.4), ('ㅅ', 'ㅈ', 0.5),
+    ('ㅇ', 'ㅎ', 0.4), ('ㅇ', 'ㅁ', 0.6), ('ㅁ', 'ㅂ', 0.5), ('ㄷ', 'ㄹ', 0.5),
+    ('ㄴ', 'ㄷ', 0.6), ('ㅂ', 'ㅍ', 0.6), ('ㄱ', 'ㄴ', 0.7), ];
+
+impl ConfusionMatrix {
+    /// Returns a matrix where every substitution costs 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the built-in matrix for typing on the 2-set layout: jamo on
+    /// touching keys cost 0.5 to swap and shift slips (ㄱ/ㄲ, ㅔ/ㅖ) 0.3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::ConfusionMatrix;
+    /// let matrix = ConfusionMatrix::dubeolsik();
+    /// assert_eq!(0.3, matrix.cost('ㄲ', 'ㄱ'));
+    /// assert_eq!(0.5, matrix.cost('ㅁ', 'ㄴ'));
+    /// assert_eq!(1.0, matrix.cost('ㅁ', 'ㅏ'));
+    /// ```
+    pub fn dubeolsik() -> Self {
+        let (adjacent, shifted) = keyboard::dubeolsik_neighbors();
+        let mut matrix = Self::new();
+        for (a, b) in adjacent {
+            matrix.set(a, b, ADJACENT_KEY_COST);
+        }
+        for (a, b) in shifted {
+            matrix.set(a, b, SHIFT_SLIP_COST);
+        }
+        matrix
+    }
+
+    /// Returns the built-in matrix for text read by OCR, where jamo that look
+    /// alike at low resolution are cheap to swap: 우/으 and 이/아 differ in a
+    /// single stroke. Use it to correct scanned documents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::{ConfusionMatrix, Dictionary};
+    /// let matrix = ConfusionMatrix::ocr();
+    /// assert!(matrix.cost('ㅜ', 'ㅡ') < matrix.cost('ㅜ', 'ㅏ'));
+    /// let dict = Dictionary::from_word_list("우리 10\n이리 10\n");
+    /// assert_eq!("우리", dict.suggest_with("으리", 1, &matrix)[0].suggestion.word);
+    /// ```
+    pub fn ocr() -> Self {
+        let mut matrix = Self::new();
+        for &(a, b, cost) in &OCR_CONFUSIONS {
+            matrix.set(a, b, cost);
+        }
+        matrix
+    }
+
+    /// Builds a matrix from lines of two jamo and a cost separated by
+    /// whitespace. Malformed lines are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::ConfusionMatrix;
+    /// let matrix = ConfusionMatrix::from_list("ㅐ ㅔ 0.2\nㅚ ㅙ\n");
+    /// assert_eq!(0.2, matrix.cost('ㅔ', 'ㅐ'));
+    /// assert_eq!(1.0, matrix.cost('ㅚ', 'ㅙ'));
+    /// ```
+    pub fn from_list(s: &str) -> Self {
+        let mut matrix = Self::new();
+        for line in s.lines() {
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            if let [a, b, cost] = fields[..] {
+                let mut chars = (a.chars(), b.chars());
+                if let (Some(a), None, Some(b), None, Ok(cost)) =
+                    (chars.0.next(), chars.0.next(), chars.1.next(), chars.1.next(), cost.parse()) {
+                    matrix.set(a, b, cost);
+                }
+            }
+        }
+        matrix
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::from_list(&fs::read_to_string(path)?))
+    }
+
+    /// Sets the cost of swapping `a` and `b`, either way round, clamped to 0..=1.
+    pub fn set(&mut self, a: char, b: char, cost: f64) {
+        let (a, b) = (compat(a), compat(b));
+        let cost = if cost.is_nan() { 1.0 } else { cost.clamp(0.0, 1.0) };
+        self.costs.insert((a, b), cost);
+        self.costs.insert((b, a), cost);
+    }
+
+    /// Returns the matrix with the cost of swapping `a` and `b` overridden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::ConfusionMatrix;
+    /// let matrix = ConfusionMatrix::dubeolsik().with('ㅐ', 'ㅔ', 0.1);
+    /// assert_eq!(0.1, matrix.cost('ㅐ', 'ㅔ'));
+    /// ```
+    pub fn with(mut self, a: char, b: char, cost: f64) -> Self {
+        self.set(a, b, cost);
+        self
+    }
+
+    /// Returns the cost of substituting `b` for `a`. A listed pair applies to
+    /// leads and tails alike, but a lead and a tail of the same consonant
+    /// still cost 1 unless listed as a pair of themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::ConfusionMatrix;
+    /// let matrix = ConfusionMatrix::dubeolsik();
+    /// assert_eq!(0.3, matrix.cost('\u{1100}', '\u{11a9}'));
+    /// assert_eq!(1.0, ConfusionMatrix::new().cost('\u{1100}', '\u{11a8}'));
+    /// ```
+    pub fn cost(&self, a: char, b: char) -> f64 {
+        if a == b {
+            return 0.0;
+        }
+        self.costs.get(&(compat(a), compat(b))).copied().unwrap_or(1.0)
+    }
+}
+
+fn compat(c: char) -> char {
+    blocks::to_compatibility(c).unwrap_or(c)
+}
+
+// Plain and tense pairs of leads and of tails, as indices into the jamo tables.
+const TENSE_LEADS: [(usize, usize); 5] = [(0, 1), (3, 4), (7, 8), (9, 10), (12, 13)];
+const TENSE_TAILS: [(usize, usize); 2] = [(1, 2), (19, 20)];
+// The seven codas a tail is pronounced as.
+const ADDED_TAILS: [usize; 7] = [1, 4, 7, 8, 16, 17, 21];
+
+/// Returns the words differing from `word` by one jamo: a tail added to a
+/// syllable without one or removed from one with one, or a lead or tail
+/// swapped between plain and tense (ㄱ/ㄲ, ㄷ/ㄸ, ㅂ/ㅃ, ㅅ/ㅆ, ㅈ/ㅉ).
+///
+/// With a dictionary, only the words it has are returned.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::spell::{minimal_pairs, Dictionary};
+/// let pairs = minimal_pairs("불", None);
+/// assert!(pairs.contains(&"뿔".to_string()));
+/// assert!(pairs.contains(&"부".to_string()));
+/// let dict = Dictionary::from_word_list("뿔\n풀\n부\n");
+/// assert_eq!(vec!["부", "뿔"], minimal_pairs("불", Some(&dict)));
+/// ```
+pub fn minimal_pairs(word: &str, dictionary: Option<&Dictionary>) -> Vec<String> {
+    let chars = word.chars().collect::<Vec<char>>();
+    let mut pairs = vec![];
+    for (i, &c) in chars.iter().enumerate() {
+        let (lead, vowel, tail) = match decompose(c) {
+            Some((l, v, t)) => (l as usize, v as usize, t as usize),
+            None => continue,
+        };
+        let mut variants = vec![];
+        if tail == 0 {
+            variants.extend(ADDED_TAILS.iter().map(|&t| (lead, t)));
+        } else {
+            variants.push((lead, 0));
+        }
+        for &(plain, tense) in &TENSE_LEADS {
+            if lead == plain || lead == tense {
+                variants.push((plain + tense - lead, tail));
+            }
+        }
+        for &(plain, tense) in &TENSE_TAILS {
+            if tail == plain || tail == tense {
+                variants.push((lead, plain + tense - tail));
+            }
+        }
+        for (lead, tail) in variants {
+            if let Ok(syllable) = blocks::compose_indices(lead, vowel, tail) {
+                let mut pair = chars.clone();
+                pair[i] = syllable;
+                pairs.push(pair.into_iter().collect::<String>());
+            }
+        }
+    }
+    pairs.retain(|p| dictionary.map_or(true, |d| d.check(p)));
+    pairs
+}
+
+/// Returns the sequence of lead consonants (초성) of `word`, keeping non-Hangul characters.
+pub fn skeleton(word: &str) -> String {
+    word.chars().map(Letter::new).map(|l| match l {
+        Letter::HangulLetter(h) => h.lead().jamo_string(),
+        Letter::OtherLetter(c) => c.to_string(),
+    }).collect::<Vec<String>>().join("")
+}
+
+/// Returns the edit distance between `a` and `b` counted in jamo rather than syllables.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::spell::jamo_distance;
+/// assert_eq!(1, jamo_distance("갑", "값"));
+/// assert_eq!(2, jamo_distance("가", "가다"));
+/// ```
+pub fn jamo_distance(a: &str, b: &str) -> usize {
+    levenshtein(&jamo_stream(a), &jamo_stream(b))
+}
+
+/// Returns the edit distance between `a` and `b` in jamo, with substitutions
+/// weighted by `matrix`.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::spell::{weighted_distance, ConfusionMatrix};
+/// let matrix = ConfusionMatrix::dubeolsik();
+/// assert_eq!(0.3, weighted_distance("각", "깍", &matrix));
+/// assert_eq!(1.0, weighted_distance("가", "나", &matrix));
+/// ```
+pub fn weighted_distance(a: &str, b: &str, matrix: &ConfusionMatrix) -> f64 {
+    weighted_levenshtein(&jamo_stream(a), &jamo_stream(b), matrix)
+}
+
+fn jamo_stream(s: &str) -> Vec<char> {
+    KoreanSentence::new(s).hangul_string().chars().collect()
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + if ca == cb { 0 } else { 1 };
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+fn weighted_levenshtein(a: &[char], b: &[char], matrix: &ConfusionMatrix) -> f64 {
+    let mut row = (0..=b.len()).map(|j| j as f64).collect::<Vec<f64>>();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = (i + 1) as f64;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + matrix.cost(ca, cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1.0).min(row[j + 1] + 1.0);
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,312 @@
+//! Normalization of speech-to-text output toward written Korean.
+//!
+//! Recognizers trained on pronunciation tend to spell words as they sound
+//! (함니다 for 합니다), read numbers out in Hangul (삼천오백원) and space
+//! particles apart from their words. [`SttNormalizer`] undoes each of these.
+
+use std::fmt;
+
+use crate::blocks;
+use crate::hangul::decompose;
+use crate::josa::PARTICLES;
+use crate::numeral::{parse_sino, LARGE_UNITS, SMALL_UNITS};
+use crate::spell::Dictionary;
+
+type SpacingHook = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+/// A preset pipeline for speech-to-text output: spacing repair, then reverse
+/// sandhi, then number re-digitization.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::stt::SttNormalizer;
+/// let normalizer = SttNormalizer::new();
+/// assert_eq!("감사합니다. 3500원 입니다", normalizer.normalize("감사함니다. 삼천오백원 임니다"));
+/// assert_eq!("학교에 2024년에 갔다", normalizer.normalize("학교 에  이천이십사년에 갔다"));
+/// ```
+pub struct SttNormalizer {
+    dictionary: Option<Dictionary>,
+    digitize: bool,
+    spacing: Vec<SpacingHook>,
+}
+
+impl fmt::Debug for SttNormalizer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SttNormalizer")
+            .field("dictionary", &self.dictionary.as_ref().map(Dictionary::len))
+            .field("digitize", &self.digitize)
+            .field("spacing", &self.spacing.len())
+            .finish()
+    }
+}
+
+impl Default for SttNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SttNormalizer {
+    /// Returns the preset: particles are attached to their words, ㅁ before
+    /// 니다 and 니까 is restored to ㅂ, and numbers are re-digitized.
+    pub fn new() -> Self {
+        SttNormalizer { dictionary: None, digitize: true, spacing: vec![Box::new(attach_particles)] }
+    }
+
+    /// Uses `dictionary` to pick among reverse sandhi candidates: a word it
+    /// does not know is replaced by the most frequent candidate it does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::spell::Dictionary;
+    /// use jamo::stt::SttNormalizer;
+    /// let dict = Dictionary::from_word_list("국물 10\n같이 10\n신라 5\n");
+    /// let normalizer = SttNormalizer::new().with_dictionary(dict);
+    /// assert_eq!("국물 같이 신라", normalizer.normalize("궁물 가치 실라"));
+    /// ```
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// Sets whether Sino-Korean numbers read out in Hangul become digits.
+    pub fn digitize(mut self, digitize: bool) -> Self {
+        self.digitize = digitize;
+        self
+    }
+
+    /// Adds a spacing repair run on the whole text, after those already added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jamo::stt::SttNormalizer;
+    /// let normalizer = SttNormalizer::new().with_spacing_hook(|s| s.replace("할 수있다", "할 수 있다"));
+    /// assert_eq!("할 수 있다", normalizer.normalize("할 수있다"));
+    /// ```
+    pub fn with_spacing_hook<F>(mut self, hook: F) -> Self
+        where F: Fn(&str) -> String + Send + Sync + 'static {
+        self.spacing.push(Box::new(hook));
+        self
+    }
+
+    /// Removes all spacing repairs, including the built-in one.
+    pub fn without_spacing_hooks(mut self) -> Self {
+        self.spacing.clear();
+        self
+    }
+
+    pub fn normalize(&self, text: &str) -> String {
+        let text = self.spacing.iter().fold(text.to_string(), |text, hook| hook(&text));
+        map_words(&text, |word| {
+            let word = self.respell(word);
+            match self.digitize {
+                true => digitize(&word).unwrap_or(word),
+                false => word,
+            }
+        })
+    }
+
+    fn respell(&self, word: &str) -> String {
+        let dictionary = match &self.dictionary {
+            Some(d) if !d.check(word) => d,
+            _ => return written_ending(word),
+        };
+        unsandhi_candidates(word, CANDIDATE_LIMIT).into_iter()
+            .filter_map(|c| dictionary.frequency(&c).map(|f| (f, c)))
+            .max_by_key(|(f, _)| *f)
+            .map_or_else(|| written_ending(word), |(_, c)| c)
+    }
+}
+
+const CANDIDATE_LIMIT: usize = 64;
+
+/// Returns spellings of `word` that the standard pronunciation rules could
+/// have turned into it, starting with `word` itself: nasalization (궁물 →
+/// 국물), ㄹ nasalization (심니 → 심리), lateralization (실라 → 신라), tensing
+/// (학꾜 → 학교), palatalization (가치 → 같이) and liaison (머거 → 먹어).
+///
+/// # Examples
+///
+/// ```
+/// use jamo::stt::unsandhi_candidates;
+/// let candidates = unsandhi_candidates("함니다", 10);
+/// assert_eq!("함니다", candidates[0]);
+/// assert!(candidates.contains(&"합니다".to_string()));
+/// assert!(unsandhi_candidates("머거", 10).contains(&"먹어".to_string()));
+/// ```
+pub fn unsandhi_candidates(word: &str, limit: usize) -> Vec<String> {
+    let chars = word.chars().collect::<Vec<char>>();
+    let boundaries = (0..chars.len()).map(|i| {
+        match (chars.get(i).copied().and_then(decompose), chars.get(i + 1).copied().and_then(decompose)) {
+            (Some(before), Some(after)) => boundary_spellings(before, after),
+            _ => vec![],
+        }
+    }).collect::<Vec<Vec<(u8, u8)>>>();
+    let mut results = vec![];
+    respellings(&boundaries, 0, &mut chars.clone(), &mut results, limit);
+    results
+}
+
+fn respellings(boundaries: &[Vec<(u8, u8)>], i: usize, current: &mut Vec<char>,
+               results: &mut Vec<String>, limit: usize) {
+    if results.len() >= limit {
+        return;
+    }
+    if i >= boundaries.len() {
+        results.push(current.iter().collect());
+        return;
+    }
+    respellings(boundaries, i + 1, current, results, limit);
+    for &(tail, lead) in &boundaries[i] {
+        let saved = (current[i], current[i + 1]);
+        if let (Some(before), Some(after)) = (with_tail(current[i], tail), with_lead(current[i + 1], lead)) {
+            current[i] = before;
+            current[i + 1] = after;
+            respellings(boundaries, i + 1, current, results, limit);
+        }
+        current[i] = saved.0;
+        current[i + 1] = saved.1;
+    }
+}
+
+// Tails a lead consonant moves back to when undoing liaison, by lead index.
+const LIAISON_TAILS: [u8; 19] = [1, 2, 4, 7, 0, 8, 16, 17, 0, 19, 20, 0, 22, 0, 23, 24, 25, 26, 0];
+const TENSE_LEADS: [(u8, u8); 5] = [(1, 0), (4, 3), (8, 7), (10, 9), (13, 12)];
+const OBSTRUENT_TAILS: [u8; 10] = [1, 2, 7, 17, 19, 20, 22, 23, 25, 26];
+
+/// Returns the written tail and lead pairs that could sound like the tail of
+/// `before` and the lead of `after`.
+fn boundary_spellings(before: (u8, u8, u8), after: (u8, u8, u8)) -> Vec<(u8, u8)> {
+    let (tail, (lead, vowel, _)) = (before.2, after);
+    let mut spellings = vec![];
+    match (tail, lead) {
+        (16, 2) => spellings.extend([(17, 2), (16, 5), (17, 5)]),
+        (16, 6) => spellings.push((17, 6)),
+        (4, 2) | (4, 6) => spellings.extend([(7, lead), (19, lead), (20, lead), (22, lead)]),
+        (21, 2) => spellings.extend([(1, 2), (21, 5), (1, 5)]),
+        (21, 6) => spellings.push((1, 6)),
+        (8, 5) => spellings.extend([(4, 5), (8, 2)]),
+        (0, 12) | (0, 14) if vowel == 20 => spellings.push((if lead == 12 { 7 } else { 25 }, 11)),
+        _ => {}
+    }
+    if OBSTRUENT_TAILS.contains(&tail) {
+        spellings.extend(TENSE_LEADS.iter().filter(|&&(tense, _)| tense == lead).map(|&(_, plain)| (tail, plain)));
+    }
+    if tail == 0 && LIAISON_TAILS[lead as usize] != 0 {
+        spellings.push((LIAISON_TAILS[lead as usize], 11));
+    }
+    spellings
+}
+
+fn with_tail(c: char, tail: u8) -> Option<char> {
+    let (l, v, _) = decompose(c)?;
+    blocks::compose_indices(l as usize, v as usize, tail as usize).ok()
+}
+
+fn with_lead(c: char, lead: u8) -> Option<char> {
+    let (_, v, t) = decompose(c)?;
+    blocks::compose_indices(lead as usize, v as usize, t as usize).ok()
+}
+
+/// Restores ㅂ in the formal endings -ㅂ니다 and -ㅂ니까, which are always
+/// pronounced with ㅁ.
+fn written_ending(word: &str) -> String {
+    let mut chars = word.chars().collect::<Vec<char>>();
+    for i in 0..chars.len().saturating_sub(2) {
+        if chars[i + 1] == '니' && matches!(chars[i + 2], '다' | '까')
+            && decompose(chars[i]).is_some_and(|(_, _, t)| t == 16) {
+            chars[i] = with_tail(chars[i], 17).unwrap_or(chars[i]);
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Joins particles that stand alone to the word before them, when they agree
+/// with it, and collapses runs of spaces. A particle that reads as a number
+/// with the counter after it, as 만 in "만 원", is left alone.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::stt::attach_particles;
+/// assert_eq!("친구가 학교에서 책을 읽었다", attach_particles("친구 가  학교 에서 책 을 읽었다"));
+/// assert_eq!("나 이 책", attach_particles("나 이 책"));
+/// assert_eq!("사과 만 원", attach_particles("사과 만 원"));
+/// assert_eq!("사과만 먹었다", attach_particles("사과 만 먹었다"));
+/// ```
+pub fn attach_particles(text: &str) -> String {
+    let mut result: Vec<String> = vec![];
+    let tokens = text.split(' ').filter(|t| !t.is_empty()).collect::<Vec<&str>>();
+    for (i, &token) in tokens.iter().enumerate() {
+        let number = tokens.get(i + 1).is_some_and(|next| digitize(&format!("{}{}", token, next)).is_some());
+        let agrees = !number && result.last().is_some_and(|previous| {
+            PARTICLES.iter().any(|p| p.form(previous) == token)
+        });
+        match (agrees, result.last_mut()) {
+            (true, Some(previous)) => previous.push_str(token),
+            _ => result.push(token.to_string()),
+        }
+    }
+    result.join(" ")
+}
+
+const COUNTERS: [&str; 14] = ["원", "년", "월", "일", "개", "명", "번", "분", "초", "층", "회", "호", "등", "퍼센트"];
+
+/// Returns `word` with a Sino-Korean number read out in Hangul written in
+/// digits, keeping a following counter and particle. Numbers without a unit
+/// such as 십 or 백 are left alone, as 이 and 사 are also words, except for
+/// months.
+///
+/// # Examples
+///
+/// ```
+/// use jamo::stt::digitize;
+/// assert_eq!(Some("3500원을".to_string()), digitize("삼천오백원을"));
+/// assert_eq!(Some("12월".to_string()), digitize("십이월"));
+/// assert_eq!(Some("2월".to_string()), digitize("이월"));
+/// assert_eq!(None, digitize("이번"));
+/// ```
+pub fn digitize(word: &str) -> Option<String> {
+    let (number, rest) = split_number(word).or_else(|| {
+        let (stem, particle) = crate::josa::split_particle(word)?;
+        split_number(stem).map(|(n, rest)| (n, format!("{}{}", rest, particle)))
+    })?;
+    Some(format!("{}{}", number, rest))
+}
+
+fn split_number(word: &str) -> Option<(u64, String)> {
+    if let Some(n) = parse_sino(word).filter(|_| has_unit(word)) {
+        return Some((n, String::new()));
+    }
+    let counter = COUNTERS.iter().find(|c| word.len() > c.len() && word.ends_with(*c))?;
+    let number = &word[..word.len() - counter.len()];
+    let n = parse_sino(number).filter(|_| has_unit(number) || *counter == "월")?;
+    Some((n, counter.to_string()))
+}
+
+fn has_unit(s: &str) -> bool {
+    s.chars().any(|c| SMALL_UNITS.iter().chain(LARGE_UNITS.iter()).any(|&(u, _)| u == c))
+}
+
+/// Replaces each whitespace-separated word of `text` by `f` of its Hangul
+/// core, keeping surrounding punctuation and the whitespace itself.
+fn map_words<F: Fn(&str) -> String>(text: &str, f: F) -> String {
+    let mut result = String::with_capacity(text.len());
+    for token in text.split_inclusive(char::is_whitespace) {
+        let word = token.trim_end_matches(char::is_whitespace);
+        let core = word.trim_matches(|c: char| !c.is_alphanumeric());
+        match core.is_empty() {
+            true => result.push_str(token),
+            false => {
+                let start = word.find(core).unwrap_or(0);
+                result.push_str(&word[..start]);
+                result.push_str(&f(core));
+                result.push_str(&token[start + core.len()..]);
+            }
+        }
+    }
+    result
+}
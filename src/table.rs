@@ -0,0 +1,75 @@
+use crate::hangul::{KoreanSentence, Letter};
+
+/// A column of [`KoreanSentence::format_table`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// The source character.
+    Original,
+    /// The source lead, vowel and tail as compatibility jamo.
+    Lead,
+    Vowel,
+    Tail,
+    /// The romanization after rules.
+    Roman,
+    /// The syllable after rules.
+    Pronounced,
+}
+
+pub const ALL_COLUMNS: [Column; 6] = [
+    Column::Original, Column::Lead, Column::Vowel, Column::Tail, Column::Roman, Column::Pronounced, ];
+
+impl Column {
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Original => "original",
+            Column::Lead => "lead",
+            Column::Vowel => "vowel",
+            Column::Tail => "tail",
+            Column::Roman => "roman",
+            Column::Pronounced => "pronounced",
+        }
+    }
+}
+
+/// Renders one row per letter of `sentence`, skipping whitespace, with the
+/// given columns aligned for a terminal. See [`KoreanSentence::format_table`].
+pub fn format_table(sentence: &KoreanSentence, columns: &[Column]) -> String {
+    let applied = sentence.applied();
+    let mut rows = vec![columns.iter().map(|c| c.header().to_string()).collect::<Vec<String>>()];
+    let letters = applied.letters().iter().zip(applied.records());
+    for (source, (letter, after)) in sentence.records().iter().zip(letters) {
+        if source.original.is_whitespace() {
+            continue;
+        }
+        let jamo = |c: Option<char>| c.map_or(String::new(), String::from);
+        rows.push(columns.iter().map(|column| match column {
+            Column::Original => source.original.to_string(),
+            Column::Lead => jamo(source.lead),
+            Column::Vowel => jamo(source.vowel),
+            Column::Tail => jamo(source.tail),
+            Column::Roman => after.roman.clone(),
+            Column::Pronounced => match letter {
+                Letter::HangulLetter(h) => h.to_string(),
+                Letter::OtherLetter(c) => c.to_string(),
+            },
+        }).collect());
+    }
+    let widths = (0..columns.len())
+        .map(|i| rows.iter().map(|r| width(&r[i])).max().unwrap_or(0))
+        .collect::<Vec<usize>>();
+    rows.iter().map(|row| {
+        let cells = row.iter().zip(&widths)
+            .map(|(cell, w)| format!("{}{}", cell, " ".repeat(w - width(cell))))
+            .collect::<Vec<String>>();
+        format!("{}\n", cells.join("  ").trim_end())
+    }).collect()
+}
+
+/// Returns the terminal width of `s`, counting Hangul as two columns.
+fn width(s: &str) -> usize {
+    s.chars().map(|c| match c {
+        '\u{1100}'..='\u{115f}' | '\u{3131}'..='\u{318e}' | '\u{ac00}'..='\u{d7a3}' => 2,
+        '\u{1160}'..='\u{11ff}' => 0,
+        _ => 1,
+    }).sum()
+}
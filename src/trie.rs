@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use crate::hangul::KoreanSentence;
+
+#[derive(Default)]
+struct Node {
+    children: HashMap<char, usize>,
+    word: Option<String>,
+}
+
+/// A prefix index over jamo keystrokes, for IME-style autocompletion.
+///
+/// Words are keyed by the keys a 2-set typist presses, so an in-progress
+/// syllable matches every word it could still grow into: "갑" matches both
+/// "갑니다" and "가방", and "값" matches "값이".
+///
+/// # Examples
+///
+/// ```
+/// use jamo::trie::JamoTrie;
+/// let mut trie = JamoTrie::new();
+/// for word in &["갑니다", "가방", "감자", "나무"] {
+///     trie.insert(word);
+/// }
+/// assert_eq!(vec!["가방", "갑니다"], trie.complete("갑", 10));
+/// assert_eq!(vec!["가방", "갑니다"], trie.complete("가ㅂ", 10));
+/// assert_eq!(vec!["가방", "감자", "갑니다"], trie.complete("가", 10));
+/// ```
+pub struct JamoTrie {
+    nodes: Vec<Node>,
+    len: usize,
+}
+
+impl Default for JamoTrie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JamoTrie {
+    pub fn new() -> Self {
+        Self { nodes: vec![Node::default()], len: 0 }
+    }
+
+    pub fn insert(&mut self, word: &str) {
+        let mut node = 0;
+        for c in KoreanSentence::new(word).strokes().chars() {
+            node = match self.nodes[node].children.get(&c) {
+                Some(&next) => next,
+                None => {
+                    self.nodes.push(Node::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(c, next);
+                    next
+                }
+            };
+        }
+        if self.nodes[node].word.is_none() {
+            self.len += 1;
+        }
+        self.nodes[node].word = Some(word.to_string());
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some_and(|node| self.nodes[node].word.is_some())
+    }
+
+    /// Returns up to `limit` words that the typed `prefix` could complete to,
+    /// in dictionary order.
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        let mut words = vec![];
+        if let Some(node) = self.find(prefix) {
+            self.collect(node, &mut words);
+        }
+        words.sort_unstable();
+        words.truncate(limit);
+        words
+    }
+
+    fn find(&self, prefix: &str) -> Option<usize> {
+        KoreanSentence::new(prefix).strokes().chars()
+            .try_fold(0, |node, c| self.nodes[node].children.get(&c).copied())
+    }
+
+    fn collect<'a>(&'a self, node: usize, words: &mut Vec<&'a str>) {
+        if let Some(word) = &self.nodes[node].word {
+            words.push(word);
+        }
+        for &child in self.nodes[node].children.values() {
+            self.collect(child, words);
+        }
+    }
+}